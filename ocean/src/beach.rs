@@ -1,23 +1,183 @@
-use crate::color::Color;
-use crate::crab::Crab;
-use crate::diet::Diet;
-use std::slice::Iter;
+use crate::crab::{Crab, CrabId, CrabIds, DeathCause};
+use crate::error::{AddCrabError, OceanError};
+use crate::genome::MutationRates;
+use crate::grid::Position;
+use crate::pedigree::Pedigree;
+use crate::rand::OceanRng;
+use std::slice::{Iter, IterMut};
+
+/**
+ * What happens when a crab is added to (or born on) a beach that is already full.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Overflow {
+    /** The crab is turned away with `OceanError::CapacityExceeded`. */
+    #[default]
+    Reject,
+    /**
+     * The weakest crab (the one with the least energy) is evicted to make room.
+     * The newcomer counts too, and loses ties, so it is turned away if it is no
+     * stronger than everyone already on the beach.
+     */
+    EvictWeakest,
+    /**
+     * The crab is let in beyond the beach's capacity for now. At the end of the
+     * tick, the ocean moves the newest crabs beyond the capacity to the nearest
+     * beaches with room, and evicts the ones with nowhere to go (see `Ocean::step`).
+     */
+    Migrate,
+}
 
 #[derive(Debug)]
 pub struct Beach {
-    // TODO: Declare the fields of the Beach struct here.
+    crabs: Vec<Crab>,
+    rng: OceanRng,
+    mutation_rates: MutationRates,
+    pub(crate) pedigree: Pedigree,
+    pub(crate) crab_ids: CrabIds,
+    capacity: Option<usize>,
+    overflow: Overflow,
+    crowding_penalty: f64,
+    position: Position,
 }
 
 impl Beach {
     pub fn new() -> Beach {
-        unimplemented!();
+        Beach {
+            crabs: Vec::new(),
+            rng: OceanRng::default(),
+            mutation_rates: MutationRates::default(),
+            pedigree: Pedigree::new(),
+            crab_ids: CrabIds::new(),
+            capacity: None,
+            overflow: Overflow::Reject,
+            crowding_penalty: 0.0,
+            position: Position::default(),
+        }
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /**
+     * Moves this beach (and the home of every crab on it) to the given position.
+     */
+    pub fn set_position(&mut self, position: Position) {
+        self.position = position;
+        for crab in self.crabs.iter_mut() {
+            crab.home = position;
+        }
+    }
+
+    /**
+     * Creates an empty beach that can hold at most `capacity` crabs.
+     */
+    pub fn with_capacity(capacity: usize) -> Beach {
+        let mut beach = Beach::new();
+        beach.capacity = Some(capacity);
+        beach
+    }
+
+    /**
+     * Returns the maximum number of crabs this beach can hold, or None if it is unlimited.
+     */
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    pub fn set_capacity(&mut self, capacity: Option<usize>) {
+        self.capacity = capacity;
+    }
+
+    /**
+     * Returns true if this beach holds as many crabs as its capacity (or more).
+     * What happens when crabs are added to a full beach depends on its `overflow`.
+     */
+    pub fn is_full(&self) -> bool {
+        self.capacity.is_some_and(|c| self.crabs.len() >= c)
+    }
+
+    pub fn overflow(&self) -> Overflow {
+        self.overflow
+    }
+
+    pub fn set_overflow(&mut self, overflow: Overflow) {
+        self.overflow = overflow;
+    }
+
+    /**
+     * Returns how much crowding hurts breeding on this beach (see `breeding_chance`).
+     * Zero unless set.
+     */
+    pub fn crowding_penalty(&self) -> f64 {
+        self.crowding_penalty
+    }
+
+    pub fn set_crowding_penalty(&mut self, penalty: f64) {
+        self.crowding_penalty = penalty;
+    }
+
+    /**
+     * Returns the chance that breeding on this beach succeeds:
+     * `1 - crowding_penalty * size / capacity`, between 0 and 1. Breeding always
+     * succeeds on a beach without a capacity.
+     */
+    pub fn breeding_chance(&self) -> f64 {
+        match self.capacity {
+            Some(capacity) => {
+                let occupancy = self.crabs.len() as f64 / capacity.max(1) as f64;
+                (1.0 - self.crowding_penalty * occupancy).clamp(0.0, 1.0)
+            }
+            None => 1.0,
+        }
+    }
+
+    /**
+     * Returns the random number generator used for breeding on this beach.
+     */
+    pub fn rng(&self) -> &OceanRng {
+        &self.rng
+    }
+
+    /**
+     * Replaces the random number generator used for breeding on this beach.
+     */
+    pub fn set_rng(&mut self, rng: OceanRng) {
+        self.rng = rng;
+    }
+
+    pub fn mutation_rates(&self) -> &MutationRates {
+        &self.mutation_rates
+    }
+
+    /**
+     * Sets the mutation rates used when breeding crabs on this beach.
+     */
+    pub fn set_mutation_rates(&mut self, rates: MutationRates) {
+        self.mutation_rates = rates;
+    }
+
+    pub(crate) fn check_capacity(&self) -> Result<(), OceanError> {
+        match self.capacity {
+            Some(capacity) if self.is_full() => Err(OceanError::CapacityExceeded { capacity }),
+            _ => Ok(()),
+        }
+    }
+
+    /**
+     * Returns the family tree of every crab that has lived on this beach.
+     */
+    pub fn pedigree(&self) -> &Pedigree {
+        &self.pedigree
     }
 
     /**
      * Returns the number of crabs on the beach.
      */
     pub fn size(&self) -> usize {
-        unimplemented!();
+        self.crabs.len()
     }
 
     /**
@@ -26,17 +186,191 @@ impl Beach {
      *   - After `add_crab` returns:
      *     - The Beach should hold the crab in its collection of crabs.
      *     - The newly added crab should be at the END of the collection.
+     *     - The crab has an id (see `Crab::id`), and is recorded in the
+     *       beach's pedigree.
+     *     - The beach is the crab's home (see `Crab::home`).
+     *
+     * Panics if the beach is full (see `is_full`) and rejects overflow. If the
+     * beach evicts its weakest crab to make room instead, the crab that does
+     * not make it is dropped (see `try_add_crab`).
      */
     pub fn add_crab(&mut self, crab: Crab) {
-        unimplemented!();
+        self.try_add_crab(crab)
+            .unwrap_or_else(|e| panic!("{}", e.error));
     }
 
+    /**
+     * Adds the crab to this beach like `add_crab`, handling a full beach
+     * according to its `overflow`.
+     *
+     * Returns the crab that was turned away to make room, if any: the weakest
+     * crab on the beach, or the newcomer itself. Returns an error holding the
+     * newcomer if the beach is full and rejects overflow, or if the newcomer's id
+     * belongs to a different crab here. A newcomer that is turned away (or
+     * rejected) is not given an id.
+     */
+    pub fn try_add_crab(&mut self, mut crab: Crab) -> Result<Option<Crab>, AddCrabError> {
+        let checked = match self.overflow {
+            Overflow::Reject => self.check_capacity(),
+            _ => Ok(()),
+        };
+        if let Err(error) = checked.and_then(|_| self.check_crab_id(&crab)) {
+            return Err(AddCrabError {
+                error,
+                crab: Box::new(crab),
+            });
+        }
+
+        let mut evicted = None;
+        if self.is_full() && self.overflow == Overflow::EvictWeakest {
+            let weakest = self
+                .crabs
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, c)| c.energy())
+                .filter(|(_, c)| c.energy() < crab.energy())
+                .map(|(i, _)| i);
+            match weakest {
+                Some(i) => evicted = Some(self.crabs.remove(i)),
+                None => return Ok(Some(crab)),
+            }
+        }
+
+        self.assign_id(&mut crab);
+        self.pedigree.record(&crab);
+        crab.home = self.position;
+        self.crabs.push(crab);
+        Ok(evicted)
+    }
+
+    /**
+     * Gives the crab the next id, if it has none yet. A crab that already has
+     * one keeps it, and later ids are kept clear of it.
+     */
+    fn assign_id(&mut self, crab: &mut Crab) {
+        if crab.id == CrabId::UNASSIGNED {
+            crab.id = self.crab_ids.next();
+        } else {
+            self.crab_ids.reserve(crab.id);
+        }
+    }
+
+    /**
+     * Returns an error if the crab already has an id, and that id belongs to a
+     * different crab on this beach or in its pedigree.
+     */
+    pub(crate) fn check_crab_id(&self, crab: &Crab) -> Result<(), OceanError> {
+        let id = crab.id;
+        if id == CrabId::UNASSIGNED {
+            return Ok(());
+        }
+        let known = self
+            .pedigree
+            .name(id)
+            .is_none_or(|name| name == crab.name() && self.pedigree.parents(id) == crab.parents());
+        if !known || self.find_crab(id).is_some() {
+            return Err(OceanError::DuplicateCrabId { id });
+        }
+        Ok(())
+    }
+
+    /**
+     * Has this beach hand out crab ids from the given counter from now on. The
+     * crabs already here keep their ids, and the counter is kept clear of them.
+     */
+    pub(crate) fn share_crab_ids(&mut self, crab_ids: &CrabIds) {
+        crab_ids.reserve_to(self.crab_ids.peek());
+        self.crab_ids = crab_ids.clone();
+    }
+
+    /**
+     * Returns the crab at the given index. Panics if the index is out of bounds.
+     */
     pub fn get_crab(&self, index: usize) -> &Crab {
-        unimplemented!();
+        self.try_get_crab(index).unwrap_or_else(|e| panic!("{}", e))
     }
 
-    pub fn crabs(&self) -> Iter<Crab> {
-        unimplemented!();
+    /**
+     * Returns the crab at the given index, or an error if the index is out of bounds.
+     */
+    pub fn try_get_crab(&self, index: usize) -> Result<&Crab, OceanError> {
+        self.crabs.get(index).ok_or(OceanError::UnknownCrab {
+            index,
+            size: self.crabs.len(),
+        })
+    }
+
+    /**
+     * Removes the crab at the given index from the beach, returning ownership of it.
+     *
+     * Crabs after `index` shift down by one. Returns an error if the index is out of bounds.
+     */
+    pub fn remove_crab(&mut self, index: usize) -> Result<Crab, OceanError> {
+        self.try_get_crab(index)?;
+        Ok(self.crabs.remove(index))
+    }
+
+    /**
+     * Returns the crab with the given id, if it lives on this beach.
+     */
+    pub fn find_crab(&self, id: CrabId) -> Option<&Crab> {
+        self.crabs.iter().find(|c| c.id() == id)
+    }
+
+    /**
+     * Removes and returns the crab with the given id. Returns an error if no
+     * crab on this beach has that id.
+     */
+    pub fn remove_crab_by_id(&mut self, id: CrabId) -> Result<Crab, OceanError> {
+        let index = self
+            .crabs
+            .iter()
+            .position(|c| c.id() == id)
+            .ok_or(OceanError::MissingCrab { id })?;
+        Ok(self.crabs.remove(index))
+    }
+
+    pub fn crabs(&self) -> Iter<'_, Crab> {
+        self.crabs.iter()
+    }
+
+    pub fn crabs_mut(&mut self) -> IterMut<'_, Crab> {
+        self.crabs.iter_mut()
+    }
+
+    /**
+     * Removes every crab that has starved to death, returning how many were removed.
+     */
+    pub fn remove_starved(&mut self) -> usize {
+        self.remove_where(|c| c.is_starved().then_some(DeathCause::Starvation))
+            .len()
+    }
+
+    /**
+     * Removes every crab that has died (see `Crab::cause_of_death`), recording
+     * why in the beach's pedigree. Returns the id and cause of death of each
+     * crab removed, in beach order.
+     */
+    pub fn remove_dead(&mut self) -> Vec<(CrabId, DeathCause)> {
+        self.remove_where(Crab::cause_of_death)
+    }
+
+    fn remove_where(
+        &mut self,
+        cause_of_death: impl Fn(&Crab) -> Option<DeathCause>,
+    ) -> Vec<(CrabId, DeathCause)> {
+        let mut dead = Vec::new();
+        self.crabs.retain(|c| match cause_of_death(c) {
+            Some(cause) => {
+                dead.push((c.id(), cause));
+                false
+            }
+            None => true,
+        });
+        for &(id, cause) in &dead {
+            self.pedigree.record_death(id, cause);
+        }
+        dead
     }
 
     /**
@@ -45,22 +379,72 @@ impl Beach {
      *   - Some of a reference to the Crab with the highest speed.
      */
     pub fn get_fastest_crab(&self) -> Option<&Crab> {
-        unimplemented!();
+        self.crabs.iter().max_by_key(|c| c.speed())
     }
 
     /**
      * Returns a vector of references to the crabs with a given name.
      */
     pub fn find_crabs_by_name(&self, name: &str) -> Vec<&Crab> {
-        unimplemented!();
+        self.crabs.iter().filter(|c| c.name() == name).collect()
     }
 
     /**
      * Breeds the `Crab`s at indices `i` and `j`, adding the new `Crab` to
      * the end of the beach's crab vector. If the indices are out of bounds,
      * the method should panic.
+     *
+     * This also panics in every other case where `try_breed_crabs` fails.
      */
     pub fn breed_crabs(&mut self, i: usize, j: usize, name: String) {
-        unimplemented!();
+        self.try_breed_crabs(i, j, name)
+            .unwrap_or_else(|e| panic!("{}", e));
+    }
+
+    /**
+     * Breeds the `Crab`s at indices `i` and `j`, adding the new `Crab` to
+     * the end of the beach's crab vector.
+     *
+     * Returns an error if either index is out of bounds, if `i == j`, if the
+     * beach is full and rejects overflow, or if crowding keeps the crabs from
+     * breeding (see `breeding_chance`). A full beach that evicts its weakest
+     * crab makes room for the baby, or leaves the baby out, as in `try_add_crab`;
+     * either way, the crab that was turned away is returned.
+     */
+    pub fn try_breed_crabs(
+        &mut self,
+        i: usize,
+        j: usize,
+        name: String,
+    ) -> Result<Option<Crab>, OceanError> {
+        self.try_get_crab(i)?;
+        self.try_get_crab(j)?;
+        if i == j {
+            return Err(OceanError::SelfBreeding { index: i });
+        }
+        if self.overflow == Overflow::Reject {
+            self.check_capacity()?;
+        }
+        let chance = self.breeding_chance();
+        if chance < 1.0 && !self.rng.chance(chance) {
+            return Err(OceanError::CrowdedOut {
+                size: self.crabs.len(),
+            });
+        }
+
+        let baby = Crab::breed(
+            &self.crabs[i],
+            &self.crabs[j],
+            name,
+            &self.mutation_rates,
+            &mut self.rng,
+        );
+        self.try_add_crab(baby).map_err(|e| e.error)
+    }
+}
+
+impl Default for Beach {
+    fn default() -> Self {
+        Beach::new()
     }
 }
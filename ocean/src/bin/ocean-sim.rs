@@ -0,0 +1,123 @@
+//! Runs a scenario file through the ocean simulation.
+//!
+//! ```text
+//! ocean-sim <SCENARIO> [--ticks N] [--seed SEED] [--csv PATH]
+//! ```
+//!
+//! The scenario is a JSON file (see `ocean::scenario::Scenario`). A summary is
+//! printed when the simulation ends, and with `--csv` per-tick statistics are
+//! also written to the given path.
+
+use ocean::report::TickReport;
+use ocean::scenario::Scenario;
+use ocean::shared;
+use std::fs;
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: ocean-sim <SCENARIO> [--ticks N] [--seed SEED] [--csv PATH]";
+
+struct Args {
+    scenario: String,
+    ticks: u64,
+    seed: Option<u64>,
+    csv: Option<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut scenario = None;
+    let mut ticks = 100;
+    let mut seed = None;
+    let mut csv = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| args.next().ok_or(format!("{} needs a value", flag));
+        match arg.as_str() {
+            "--ticks" => {
+                ticks = value("--ticks")?
+                    .parse()
+                    .map_err(|e| format!("invalid --ticks: {}", e))?
+            }
+            "--seed" => {
+                seed = Some(
+                    value("--seed")?
+                        .parse()
+                        .map_err(|e| format!("invalid --seed: {}", e))?,
+                )
+            }
+            "--csv" => csv = Some(value("--csv")?),
+            _ if arg.starts_with("--") => return Err(format!("unknown option {}", arg)),
+            _ if scenario.is_none() => scenario = Some(arg),
+            _ => return Err(format!("unexpected argument {}", arg)),
+        }
+    }
+
+    Ok(Args {
+        scenario: scenario.ok_or("missing scenario file")?,
+        ticks,
+        seed,
+        csv,
+    })
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let json = fs::read_to_string(&args.scenario)
+        .map_err(|e| format!("cannot read {}: {}", args.scenario, e))?;
+    let mut scenario =
+        Scenario::from_json(&json).map_err(|e| format!("invalid scenario: {}", e))?;
+    if let Some(seed) = args.seed {
+        scenario.seed = seed;
+    }
+
+    let mut ocean = scenario
+        .build()
+        .map_err(|e| format!("invalid scenario: {}", e))?;
+    let reports = ocean.run(args.ticks);
+
+    if let Some(path) = &args.csv {
+        let mut csv = String::from(TickReport::CSV_HEADER);
+        csv.push('\n');
+        for report in &reports {
+            csv.push_str(&report.to_csv_row());
+            csv.push('\n');
+        }
+        fs::write(path, csv).map_err(|e| format!("cannot write {}: {}", path, e))?;
+    }
+
+    let total = |f: fn(&TickReport) -> usize| reports.iter().map(f).sum::<usize>();
+    println!("Simulated {} ticks (seed {})", ocean.tick(), scenario.seed);
+    for (i, beach) in ocean.beaches().enumerate() {
+        println!("  beach {}: {} crabs", i, beach.size());
+    }
+    println!("  successful hunts: {}", total(TickReport::fed));
+    println!("  failed hunts:     {}", total(TickReport::hungry));
+    println!("  starved:          {}", total(TickReport::starved));
+    println!("  died of old age:  {}", total(TickReport::died_of_old_age));
+    println!("  migrated:         {}", total(TickReport::migrated));
+    println!("  evicted:          {}", total(TickReport::evicted));
+    println!(
+        "  prey remaining:   {}",
+        ocean
+            .reefs()
+            .map(|r| shared::read(r).population())
+            .sum::<usize>()
+    );
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("error: {}\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
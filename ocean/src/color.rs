@@ -1,4 +1,5 @@
-#[derive(Eq, PartialEq, Debug)]
+#[derive(Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Color {
     pub r: u8,
     pub g: u8,
@@ -34,6 +35,10 @@ impl Color {
      * https://doc.rust-lang.org/std/primitive.u8.html
      */
     pub fn cross(c1: &Color, c2: &Color) -> Color {
-        unimplemented!();
+        Color::new(
+            c1.r.wrapping_add(c2.r),
+            c1.g.wrapping_add(c2.g),
+            c1.b.wrapping_add(c2.b),
+        )
     }
 }
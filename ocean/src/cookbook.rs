@@ -1,41 +1,387 @@
 use crate::diet::Diet;
+use crate::prey::{Prey, PreyKind, MAX_FRESHNESS};
+use std::collections::BTreeSet;
+use std::fmt;
 
-#[derive(Debug)]
+/**
+ * A collection of recipes.
+ *
+ * With the `serde` feature, cookbooks can be loaded from TOML (or the equivalent JSON):
+ *
+ * ```toml
+ * [[recipes]]
+ * name = "chowder"
+ * diets = ["Shellfish"]
+ * tags = ["soup"]
+ * ingredients = [{ prey = "clam", quantity = 2 }]
+ * popularity = 5
+ * min_freshness = 50
+ * ```
+ *
+ * Ingredients name prey by their `PreyRecord` tag, so custom prey can be used too.
+ */
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cookbook {
+    #[cfg_attr(feature = "serde", serde(default))]
     recipes: Vec<Recipe>,
 }
 
+#[derive(Debug)]
+pub enum CookbookError {
+    #[cfg(feature = "serde")]
+    Json(serde_json::Error),
+    #[cfg(feature = "serde")]
+    Toml(toml::de::Error),
+    /** The recipe at this index in the file has an empty name. */
+    UnnamedRecipe { index: usize },
+    /** Two recipes have the same name. */
+    DuplicateRecipe { name: String },
+    /** The named recipe does not require any diet. */
+    NoDiets { recipe: String },
+    /** The named recipe calls for zero of some prey. */
+    EmptyIngredient { recipe: String, prey: PreyKind },
+    /** The named recipe lists the same prey as more than one ingredient. */
+    DuplicateIngredient { recipe: String, prey: PreyKind },
+    /** The named recipe requires more than `MAX_FRESHNESS`. */
+    ImpossibleFreshness { recipe: String, min_freshness: u32 },
+}
+
+impl fmt::Display for CookbookError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            #[cfg(feature = "serde")]
+            CookbookError::Json(e) => write!(f, "invalid JSON cookbook: {}", e),
+            #[cfg(feature = "serde")]
+            CookbookError::Toml(e) => write!(f, "invalid TOML cookbook: {}", e),
+            CookbookError::UnnamedRecipe { index } => write!(f, "recipe {} has no name", index),
+            CookbookError::DuplicateRecipe { name } => {
+                write!(f, "more than one recipe is named {}", name)
+            }
+            CookbookError::NoDiets { recipe } => write!(f, "recipe {} has no diets", recipe),
+            CookbookError::EmptyIngredient { recipe, prey } => {
+                write!(f, "recipe {} calls for no {}", recipe, prey)
+            }
+            CookbookError::DuplicateIngredient { recipe, prey } => {
+                write!(f, "recipe {} lists {} more than once", recipe, prey)
+            }
+            CookbookError::ImpossibleFreshness {
+                recipe,
+                min_freshness,
+            } => write!(
+                f,
+                "recipe {} requires freshness {}, but the maximum is {}",
+                recipe, min_freshness, MAX_FRESHNESS
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CookbookError {}
+
 impl Cookbook {
     pub fn new() -> Cookbook {
-        let chowder = Recipe::new(String::from("chowder"), Diet::Shellfish);
-        let cioppino = Recipe::new(String::from("cioppino"), Diet::Fish);
+        let chowder = Recipe::new(String::from("chowder"), Diet::Shellfish)
+            .with_ingredient(PreyKind::Clam, 2)
+            .with_tag("soup")
+            .with_popularity(5);
+        let cioppino = Recipe::new(String::from("cioppino"), Diet::Fish)
+            .with_ingredient(PreyKind::Minnow, 2)
+            .with_tag("stew")
+            .with_popularity(3);
 
         Cookbook {
             recipes: vec![chowder, cioppino],
         }
     }
 
-    pub fn recipes(&self) -> std::slice::Iter<Recipe> {
+    /**
+     * Builds and validates a cookbook from the given recipes.
+     */
+    pub fn from_recipes(recipes: Vec<Recipe>) -> Result<Cookbook, CookbookError> {
+        let cookbook = Cookbook { recipes };
+        cookbook.validate()?;
+        Ok(cookbook)
+    }
+
+    /**
+     * Parses and validates a cookbook from TOML.
+     */
+    #[cfg(feature = "serde")]
+    pub fn from_toml(text: &str) -> Result<Cookbook, CookbookError> {
+        let cookbook: Cookbook = toml::from_str(text).map_err(CookbookError::Toml)?;
+        cookbook.validate()?;
+        Ok(cookbook)
+    }
+
+    /**
+     * Parses and validates a cookbook from JSON.
+     */
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<Cookbook, CookbookError> {
+        let cookbook: Cookbook = serde_json::from_str(json).map_err(CookbookError::Json)?;
+        cookbook.validate()?;
+        Ok(cookbook)
+    }
+
+    /**
+     * Checks that every recipe has a unique, non-empty name and at least one diet,
+     * that no ingredient has a quantity of zero or is listed twice, and that the
+     * minimum freshness can be met.
+     */
+    pub fn validate(&self) -> Result<(), CookbookError> {
+        let mut names = BTreeSet::new();
+        for (index, recipe) in self.recipes.iter().enumerate() {
+            if recipe.name.is_empty() {
+                return Err(CookbookError::UnnamedRecipe { index });
+            }
+            if !names.insert(&recipe.name) {
+                return Err(CookbookError::DuplicateRecipe {
+                    name: recipe.name.clone(),
+                });
+            }
+            if recipe.diets.is_empty() {
+                return Err(CookbookError::NoDiets {
+                    recipe: recipe.name.clone(),
+                });
+            }
+            if let Some(i) = recipe.ingredients.iter().find(|i| i.quantity == 0) {
+                return Err(CookbookError::EmptyIngredient {
+                    recipe: recipe.name.clone(),
+                    prey: i.prey.clone(),
+                });
+            }
+            let mut kinds = BTreeSet::new();
+            if let Some(i) = recipe.ingredients.iter().find(|i| !kinds.insert(&i.prey)) {
+                return Err(CookbookError::DuplicateIngredient {
+                    recipe: recipe.name.clone(),
+                    prey: i.prey.clone(),
+                });
+            }
+            if recipe.min_freshness > MAX_FRESHNESS {
+                return Err(CookbookError::ImpossibleFreshness {
+                    recipe: recipe.name.clone(),
+                    min_freshness: recipe.min_freshness,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn recipes(&self) -> std::slice::Iter<'_, Recipe> {
         self.recipes.iter()
     }
+
+    /**
+     * Returns the recipe with the given name, if any.
+     */
+    pub fn recipe(&self, name: &str) -> Option<&Recipe> {
+        self.recipes.iter().find(|r| r.name == name)
+    }
 }
 
-#[derive(Debug)]
+impl Default for Cookbook {
+    fn default() -> Self {
+        Cookbook::new()
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Recipe {
     name: String,
-    diet: Diet,
+    diets: Vec<Diet>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    ingredients: Vec<Ingredient>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    tags: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    popularity: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    min_freshness: u32,
 }
 
 impl Recipe {
     pub fn new(name: String, diet: Diet) -> Recipe {
-        Recipe { name, diet }
+        Recipe {
+            name,
+            diets: vec![diet],
+            ingredients: Vec::new(),
+            tags: Vec::new(),
+            popularity: 0,
+            min_freshness: 0,
+        }
+    }
+
+    /**
+     * Returns this recipe, also requiring the given diet.
+     */
+    pub fn with_diet(mut self, diet: Diet) -> Recipe {
+        if !self.diets.contains(&diet) {
+            self.diets.push(diet);
+        }
+        self
+    }
+
+    /**
+     * Returns this recipe, also calling for `quantity` of the given kind of prey.
+     */
+    pub fn with_ingredient(mut self, prey: PreyKind, quantity: u32) -> Recipe {
+        self.ingredients.push(Ingredient { prey, quantity });
+        self
+    }
+
+    pub fn with_tag(mut self, tag: &str) -> Recipe {
+        self.tags.push(String::from(tag));
+        self
+    }
+
+    pub fn with_popularity(mut self, popularity: u32) -> Recipe {
+        self.popularity = popularity;
+        self
+    }
+
+    pub fn with_min_freshness(mut self, min_freshness: u32) -> Recipe {
+        self.min_freshness = min_freshness;
+        self
+    }
+
+    /**
+     * Returns the recipe's main diet: the first of the diets it requires, or None
+     * for a recipe (from an unvalidated cookbook) that requires none.
+     */
+    pub fn diet(&self) -> Option<Diet> {
+        self.diets.first().copied()
     }
 
-    pub fn diet(&self) -> Diet {
-        self.diet
+    /**
+     * Returns every diet a crab must eat to enjoy this recipe.
+     */
+    pub fn diets(&self) -> &[Diet] {
+        &self.diets
     }
 
     pub fn name(&self) -> &String {
         &self.name
     }
+
+    pub fn ingredients(&self) -> &[Ingredient] {
+        &self.ingredients
+    }
+
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /**
+     * Returns how well-liked this recipe is among crabs in general. Zero unless set.
+     */
+    pub fn popularity(&self) -> u32 {
+        self.popularity
+    }
+
+    /**
+     * Returns the freshness (see `Prey::freshness`) every ingredient must have
+     * for this recipe. Zero unless set.
+     */
+    pub fn min_freshness(&self) -> u32 {
+        self.min_freshness
+    }
+
+    /**
+     * Returns true if the given prey can be used as the ingredient `ingredient`
+     * of this recipe: it is the right kind, and fresh enough.
+     */
+    pub fn accepts(&self, ingredient: &Ingredient, prey: &dyn Prey) -> bool {
+        &prey.kind() == ingredient.prey() && prey.freshness() >= self.min_freshness
+    }
+}
+
+/**
+ * How much each factor counts when a crab ranks recipes (see `Crab::choose_recipes`).
+ *
+ * A recipe's score is
+ *
+ * ```text
+ * diet * (the crab's weight for the least-liked of the recipe's diets)
+ *   + ingredients * (the fraction of the recipe's ingredients in the crab's pantry,
+ *                    fresh enough for the recipe)
+ *   + popularity * (the recipe's popularity)
+ * ```
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecipeScoring {
+    pub diet: f64,
+    pub ingredients: f64,
+    pub popularity: f64,
+}
+
+impl Default for RecipeScoring {
+    fn default() -> Self {
+        RecipeScoring {
+            diet: 1.0,
+            ingredients: 1.0,
+            popularity: 0.1,
+        }
+    }
+}
+
+/**
+ * Some quantity of one kind of prey, called for by a recipe.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "IngredientRecord", into = "IngredientRecord")
+)]
+pub struct Ingredient {
+    prey: PreyKind,
+    quantity: u32,
+}
+
+impl Ingredient {
+    pub fn new(prey: PreyKind, quantity: u32) -> Ingredient {
+        Ingredient { prey, quantity }
+    }
+
+    pub fn prey(&self) -> &PreyKind {
+        &self.prey
+    }
+
+    pub fn quantity(&self) -> u32 {
+        self.quantity
+    }
+}
+
+/**
+ * An ingredient as written in a cookbook file, naming its prey by tag.
+ */
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct IngredientRecord {
+    prey: String,
+    quantity: u32,
+}
+
+#[cfg(feature = "serde")]
+impl From<IngredientRecord> for Ingredient {
+    fn from(record: IngredientRecord) -> Self {
+        Ingredient::new(PreyKind::from_tag(&record.prey), record.quantity)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Ingredient> for IngredientRecord {
+    fn from(ingredient: Ingredient) -> Self {
+        IngredientRecord {
+            prey: String::from(ingredient.prey.tag()),
+            quantity: ingredient.quantity,
+        }
+    }
 }
@@ -1,36 +1,439 @@
 use crate::color::Color;
-use crate::cookbook::{Cookbook, Recipe};
-use crate::diet::Diet;
+use crate::cookbook::{Cookbook, Recipe, RecipeScoring};
+use crate::diet::{Diet, DietSet};
+use crate::error::OceanError;
+use crate::genome::{Genome, MutationRates};
+use crate::grid::Position;
+use crate::pantry::Pantry;
 use crate::prey::Prey;
+use crate::rand::OceanRng;
 use crate::reef::Reef;
-use std::cell::RefCell;
-use std::rc::Rc;
+use crate::report::HuntReport;
+use crate::shared::{self, Shared, SharedReef};
+use crate::strategy::{HuntStrategy, InOrder};
+use std::fmt;
+
+/**
+ * A stable, unique identifier for a crab.
+ *
+ * A crab is given its id when it is first added to a beach (see `Beach::add_crab`).
+ * Ids are handed out in increasing order by a counter that every beach in an
+ * ocean shares, so a crab's id is unique within its ocean and always greater
+ * than the ids of its parents.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CrabId(u64);
+
+impl CrabId {
+    /** The id of a crab that has not been added to a beach yet. */
+    pub const UNASSIGNED: CrabId = CrabId(u64::MAX);
+
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+/**
+ * Hands out crab ids in increasing order. Clones share the same counter.
+ */
+#[derive(Debug, Clone)]
+pub(crate) struct CrabIds(Shared<u64>);
+
+impl CrabIds {
+    pub(crate) fn new() -> CrabIds {
+        CrabIds(shared::share(0))
+    }
+
+    pub(crate) fn next(&self) -> CrabId {
+        let mut next = shared::write(&self.0);
+        let id = CrabId(*next);
+        // Saturate rather than overflow, and never hand out `CrabId::UNASSIGNED`.
+        *next = next.saturating_add(1).min(u64::MAX - 1);
+        id
+    }
+
+    /**
+     * Returns the value of the next id that will be handed out.
+     */
+    pub(crate) fn peek(&self) -> u64 {
+        *shared::read(&self.0)
+    }
+
+    /**
+     * Makes sure every id handed out from now on is greater than `id`.
+     */
+    pub(crate) fn reserve(&self, id: CrabId) {
+        self.reserve_to(id.0.saturating_add(1));
+    }
+
+    /**
+     * Makes sure every id handed out from now on is at least `value`.
+     */
+    pub(crate) fn reserve_to(&self, value: u64) {
+        let mut next = shared::write(&self.0);
+        *next = (*next).max(value.min(u64::MAX - 1));
+    }
+}
+
+impl fmt::Display for CrabId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/**
+ * Why a crab died.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DeathCause {
+    /** The crab ran out of energy (see `Crab::is_starved`). */
+    Starvation,
+    /** The crab reached the end of its lifespan (see `Crab::lifespan`). */
+    OldAge,
+}
+
+impl fmt::Display for DeathCause {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DeathCause::Starvation => write!(f, "starvation"),
+            DeathCause::OldAge => write!(f, "old age"),
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct Crab {
-    // TODO: Add fields here (some in part 1, some in part 2)
+    pub(crate) id: CrabId,
+    pub(crate) parents: Option<(CrabId, CrabId)>,
+    name: String,
+    speed: u32,
+    genome: Genome,
+    diets: DietSet,
+    pub(crate) reefs: Vec<SharedReef>,
+    /** For each reef in `reefs`, the number of ticks in a row it has been seen empty. */
+    pub(crate) reef_empty_ticks: Vec<u32>,
+    strategy: Box<dyn HuntStrategy>,
+    pantry: Pantry,
+    pub(crate) home: Position,
+    pub(crate) hungry: bool,
+    pub(crate) energy: u32,
+    pub(crate) age: u32,
+    lifespan: Option<u32>,
 }
 
 // Do NOT implement Copy for Crab.
 impl Crab {
+    /** The energy every crab starts out with. */
+    pub const INITIAL_ENERGY: u32 = 10;
+
+    /** A crab cannot store more energy than this, no matter how much it eats. */
+    pub const MAX_ENERGY: u32 = 20;
+
+    /** The energy a crab burns every tick just to stay alive. */
+    pub const METABOLISM: u32 = 1;
+
+    /** The fraction of its lifespan after which a crab starts to slow down. */
+    pub const PRIME: f64 = 0.75;
+
+    /** How far a crab can travel from home for each point of speed (see `range`). */
+    pub const RANGE_PER_SPEED: u32 = 2;
+
     pub fn new(name: String, speed: u32, color: Color, diet: Diet) -> Crab {
-        unimplemented!();
+        Crab::from_genome(name, speed, Genome::new(speed, color, diet))
+    }
+
+    /**
+     * Creates a crab with the given genome, currently moving at `speed`.
+     *
+     * The crab eats only the diet in its genome (see `with_diets`).
+     */
+    pub fn from_genome(name: String, speed: u32, genome: Genome) -> Crab {
+        Crab {
+            id: CrabId::UNASSIGNED,
+            parents: None,
+            name,
+            speed,
+            diets: DietSet::only(genome.diet()),
+            genome,
+            reefs: Vec::new(),
+            reef_empty_ticks: Vec::new(),
+            strategy: Box::new(InOrder),
+            pantry: Pantry::new(),
+            home: Position::default(),
+            hungry: false,
+            energy: Crab::INITIAL_ENERGY,
+            age: 0,
+            lifespan: None,
+        }
+    }
+
+    /**
+     * Returns this crab, eating the given set of diets instead of only the diet
+     * in its genome.
+     */
+    pub fn with_diets(mut self, diets: DietSet) -> Crab {
+        self.diets = diets;
+        self
+    }
+
+    /**
+     * Returns this crab, dying of old age once it is `lifespan` ticks old.
+     */
+    pub fn with_lifespan(mut self, lifespan: u32) -> Crab {
+        self.lifespan = Some(lifespan);
+        self
+    }
+
+    /**
+     * Returns this crab, hunting with the given strategy instead of the default
+     * (`InOrder`).
+     */
+    pub fn with_strategy(mut self, strategy: Box<dyn HuntStrategy>) -> Crab {
+        self.strategy = strategy;
+        self
+    }
+
+    /**
+     * Breeds two crabs, producing a new baby crab with the given name.
+     *
+     *   - The baby records `c1` and `c2` as its parents.
+     *   - The baby's genome is a crossover of its parents' genomes, mutated
+     *     according to `rates` (see `Genome::crossover`).
+     *   - Babies go slowly: the baby's speed is 1, and it grows up to the
+     *     speed in its genome one tick at a time (see `grow`).
+     *   - The baby eats whatever both of its parents eat, as well as the diet
+     *     in its own genome.
+     *   - Hunting strategies are learned, not inherited: the baby hunts with
+     *     the default strategy.
+     */
+    pub fn breed(
+        c1: &Crab,
+        c2: &Crab,
+        name: String,
+        rates: &MutationRates,
+        rng: &mut OceanRng,
+    ) -> Crab {
+        let genome = Genome::crossover(&c1.genome, &c2.genome, rates, rng);
+        let mut baby = Crab::from_genome(name, 1, genome);
+        baby.parents = Some((c1.id, c2.id));
+        baby.diets = c1.diets.intersection(&c2.diets) | baby.diet();
+        baby
+    }
+
+    /**
+     * Returns this crab's id, or `CrabId::UNASSIGNED` if it has not been added
+     * to a beach yet.
+     */
+    pub fn id(&self) -> CrabId {
+        self.id
+    }
+
+    /**
+     * Returns the ids of this crab's parents, or None if it was not bred (see `breed`).
+     */
+    pub fn parents(&self) -> Option<(CrabId, CrabId)> {
+        self.parents
     }
 
     pub fn name(&self) -> &str {
-        unimplemented!();
+        &self.name
     }
 
     pub fn speed(&self) -> u32 {
-        unimplemented!();
+        self.speed
     }
 
     pub fn color(&self) -> &Color {
-        unimplemented!();
+        self.genome.color()
     }
 
+    /**
+     * Returns the diet in this crab's genome. See `diets` for everything it eats.
+     */
     pub fn diet(&self) -> Diet {
-        unimplemented!();
+        self.genome.diet()
+    }
+
+    pub fn diets(&self) -> &DietSet {
+        &self.diets
+    }
+
+    pub fn set_diets(&mut self, diets: DietSet) {
+        self.diets = diets;
+    }
+
+    pub fn genome(&self) -> &Genome {
+        &self.genome
+    }
+
+    pub fn strategy(&self) -> &dyn HuntStrategy {
+        self.strategy.as_ref()
+    }
+
+    pub fn set_strategy(&mut self, strategy: Box<dyn HuntStrategy>) {
+        self.strategy = strategy;
+    }
+
+    pub fn pantry(&self) -> &Pantry {
+        &self.pantry
+    }
+
+    pub fn pantry_mut(&mut self) -> &mut Pantry {
+        &mut self.pantry
+    }
+
+    /**
+     * Returns the position of the beach this crab lives on (the origin until it
+     * is added to a beach).
+     */
+    pub fn home(&self) -> Position {
+        self.home
+    }
+
+    /**
+     * Returns how far from home this crab can travel to discover a reef:
+     * `RANGE_PER_SPEED` for each point of its current speed.
+     */
+    pub fn range(&self) -> u32 {
+        self.speed.saturating_mul(Crab::RANGE_PER_SPEED)
+    }
+
+    /**
+     * Returns true if the given reef is within this crab's range of its home.
+     */
+    pub fn can_reach(&self, reef: &Reef) -> bool {
+        self.home.distance(&reef.position()) <= f64::from(self.range())
+    }
+
+    /**
+     * Returns the number of ticks it takes this crab to travel from home to the given reef.
+     */
+    pub fn travel_time(&self, reef: &Reef) -> u32 {
+        self.home.travel_time(&reef.position(), self.speed)
+    }
+
+    /**
+     * Grows this crab's speed by one, up to the speed in its genome.
+     *
+     * Crabs past their prime (see `is_past_prime`) slow down by one instead,
+     * down to a speed of 1.
+     */
+    pub fn grow(&mut self) {
+        if self.is_past_prime() {
+            self.speed = self.speed.saturating_sub(1).max(1);
+        } else if self.speed < self.genome.speed() {
+            self.speed += 1;
+        }
+    }
+
+    /**
+     * Returns the number of ticks this crab has lived (see `grow_older`).
+     */
+    pub fn age(&self) -> u32 {
+        self.age
+    }
+
+    /**
+     * Makes this crab one tick older.
+     */
+    pub fn grow_older(&mut self) {
+        self.age = self.age.saturating_add(1);
+    }
+
+    /**
+     * Returns the age at which this crab dies of old age, or None if it never does.
+     */
+    pub fn lifespan(&self) -> Option<u32> {
+        self.lifespan
+    }
+
+    pub fn set_lifespan(&mut self, lifespan: Option<u32>) {
+        self.lifespan = lifespan;
+    }
+
+    /**
+     * Returns true if this crab has lived through the first `PRIME` of its
+     * lifespan, and is slowing down (see `grow`). Crabs without a lifespan
+     * never get old.
+     */
+    pub fn is_past_prime(&self) -> bool {
+        self.lifespan
+            .is_some_and(|lifespan| f64::from(self.age) >= Crab::PRIME * f64::from(lifespan))
+    }
+
+    /**
+     * Returns true if this crab has reached the end of its lifespan.
+     */
+    pub fn is_dead_of_old_age(&self) -> bool {
+        self.lifespan.is_some_and(|lifespan| self.age >= lifespan)
+    }
+
+    /**
+     * Returns why this crab has died, or None if it is still alive. A crab that
+     * has both starved and reached the end of its lifespan starved.
+     */
+    pub fn cause_of_death(&self) -> Option<DeathCause> {
+        if self.is_starved() {
+            Some(DeathCause::Starvation)
+        } else if self.is_dead_of_old_age() {
+            Some(DeathCause::OldAge)
+        } else {
+            None
+        }
+    }
+
+    /**
+     * Returns true if this crab failed to catch anything on its last hunt.
+     */
+    pub fn is_hungry(&self) -> bool {
+        self.hungry
+    }
+
+    pub fn energy(&self) -> u32 {
+        self.energy
+    }
+
+    /**
+     * Returns true if this crab has run out of energy and starved to death.
+     */
+    pub fn is_starved(&self) -> bool {
+        self.energy == 0
+    }
+
+    /**
+     * Eats the given prey, gaining its nutritional value in energy (up to `MAX_ENERGY`).
+     *
+     * This takes ownership of the prey: once eaten, it is gone for good.
+     */
+    pub fn eat(&mut self, prey: Box<dyn Prey>) {
+        self.energy = self
+            .energy
+            .saturating_add(prey.nutrition())
+            .min(Crab::MAX_ENERGY);
+    }
+
+    /**
+     * Eats the oldest prey in this crab's pantry that it can eat, if any.
+     * Returns true if the crab ate something.
+     */
+    pub fn eat_from_pantry(&mut self) -> bool {
+        let diets = self.diets;
+        match self.pantry.take_where(|p| diets.contains(p.diet())) {
+            Some(prey) => {
+                self.eat(prey);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /**
+     * Burns one tick's worth of energy. A crab whose energy reaches zero has starved.
+     */
+    pub fn metabolize(&mut self) {
+        self.energy = self.energy.saturating_sub(Crab::METABOLISM);
     }
 
     // PART 2 BELOW
@@ -39,79 +442,363 @@ impl Crab {
     /**
      * Have this crab discover a new reef, adding it to its list of reefs.
      */
-    pub fn discover_reef(&mut self, reef: Rc<RefCell<Reef>>) {
-        unimplemented!();
+    pub fn discover_reef(&mut self, reef: SharedReef) {
+        self.reefs.push(reef);
+        self.reef_empty_ticks.push(0);
+    }
+
+    /**
+     * Returns the reefs this crab knows of, in the order it discovered them.
+     */
+    pub fn reefs(&self) -> std::slice::Iter<'_, SharedReef> {
+        self.reefs.iter()
+    }
+
+    /**
+     * Returns true if this crab knows of the given reef.
+     */
+    pub fn knows_reef(&self, reef: &SharedReef) -> bool {
+        self.reefs.iter().any(|r| shared::ptr_eq(r, reef))
     }
 
     /**
-     * Returns Some prey from one of the reefs this crab feeds from,
-     * and the index of that reef in self.reefs if able to find Some prey
-     * using the `take_prey` method of Reef.
+     * Forgets the reef at the given index, returning it, or None if there is no
+     * such reef. Reefs discovered after it move down by one index.
+     */
+    pub fn forget_reef(&mut self, index: usize) -> Option<SharedReef> {
+        if index >= self.reefs.len() {
+            return None;
+        }
+        self.reef_empty_ticks.remove(index);
+        self.strategy.forget_reef(index);
+        Some(self.reefs.remove(index))
+    }
+
+    /**
+     * Returns the number of ticks in a row the reef at the given index has been
+     * seen empty (see `forget_empty_reefs`), or None if there is no such reef.
+     */
+    pub fn reef_empty_ticks(&self, index: usize) -> Option<u32> {
+        self.reef_empty_ticks.get(index).copied()
+    }
+
+    /**
+     * Checks on every reef this crab knows of, counting how many ticks in a row
+     * each has been empty, and forgets those that have been empty for `after`
+     * ticks. A reef with prey in it starts counting again from zero. Crabs never
+     * forget reefs if `after` is zero.
      *
-     * If `take_prey` returns None, try the next reef. Try each reef only once.
+     * Returns the number of reefs forgotten.
+     */
+    pub fn forget_empty_reefs(&mut self, after: u32) -> usize {
+        for (reef, ticks) in self.reefs.iter().zip(self.reef_empty_ticks.iter_mut()) {
+            if shared::read(reef).population() == 0 {
+                *ticks += 1;
+            } else {
+                *ticks = 0;
+            }
+        }
+        if after == 0 {
+            return 0;
+        }
+
+        let mut forgotten = 0;
+        let mut i = 0;
+        while i < self.reefs.len() {
+            if self.reef_empty_ticks[i] >= after {
+                self.forget_reef(i);
+                forgotten += 1;
+            } else {
+                i += 1;
+            }
+        }
+        forgotten
+    }
+
+    /**
+     * Like `discover_reef`, but only if the reef is within this crab's range
+     * (see `can_reach`). Returns an error otherwise.
+     */
+    pub fn try_discover_reef(&mut self, reef: SharedReef) -> Result<(), OceanError> {
+        let (reachable, distance) = {
+            let r = shared::read(&reef);
+            (self.can_reach(&r), self.home.distance(&r.position()))
+        };
+        if !reachable {
+            return Err(OceanError::OutOfRange {
+                distance: distance.ceil() as u32,
+                range: self.range(),
+            });
+        }
+        self.discover_reef(reef);
+        Ok(())
+    }
+
+    /**
+     * Returns Some prey from the reef this crab's strategy chooses to hunt in,
+     * and the index of that reef in self.reefs.
      *
-     * If all reefs are empty, or this crab has no reefs, return None.
+     * If all reefs are empty, this crab has no reefs, or the strategy gives up,
+     * return None.
+     */
+    fn catch_prey(&mut self, attempts: u32) -> Option<(Box<dyn Prey>, usize)> {
+        let populations: Vec<usize> = self
+            .reefs
+            .iter()
+            .map(|reef| shared::read(reef).population())
+            .collect();
+        if populations.iter().all(|&n| n == 0) {
+            return None;
+        }
+
+        let reef_index = self.strategy.choose_reef(&populations, attempts)?;
+        let prey = shared::write(self.reefs.get(reef_index)?).take_prey()?;
+        Some((prey, reef_index))
+    }
+
+    /**
+     * Travels to the reef at the given index, unless this crab has already been
+     * there on this hunt, burning `METABOLISM` energy for every tick on the way.
      */
-    fn catch_prey(&mut self) -> Option<(Box<dyn Prey>, usize)> {
-        unimplemented!();
+    fn travel_to(&mut self, reef_index: usize, report: &mut HuntReport) {
+        if report.visited().contains(&reef_index) {
+            return;
+        }
+        let ticks = self.travel_time(&shared::read(&self.reefs[reef_index]));
+        report.record_travel(reef_index, ticks);
+        self.energy = self
+            .energy
+            .saturating_sub(ticks.saturating_mul(Crab::METABOLISM));
     }
 
     /**
      * Releases the given prey back into the reef at the given index.
      */
     fn release_prey(&mut self, prey: Box<dyn Prey>, reef_index: usize) {
-        unimplemented!();
+        shared::write(&self.reefs[reef_index]).add_prey(prey);
     }
 
     /**
      * Have this crab go hunting.
      *
-     * A crab will keep trying to catch prey until it succeeds,
-     * or runs out of remaining prey to try to catch.
-     *
-     * You should keep track of all escaped prey in a local.
+     * A crab will keep trying to catch prey until it succeeds, runs out of
+     * remaining prey to try to catch, or its strategy gives up. Its
+     * `HuntStrategy` chooses which reef to try each time.
      *
-     * Once you have finished hunting, release all escaped prey back into
-     * the reefs from whence they came _before_ you return if prey was caught.
+     * Caught prey is eaten on the spot, unless the crab already has all the
+     * energy it can hold: then it stores the prey in its pantry for later, if
+     * there is room. All escaped (or inedible) prey are
+     * released back into the reefs from whence they came before returning.
+     * The crab is left hungry if nothing was caught.
      *
-     * Your algorithm might look something like this pseudocode. The challenge
-     * in this task is not intended to be in figuring out the algorithm, but
-     * in figuring out _how to write it in Rust_ using what you've learned so far.
+     * Getting to a reef takes time (see `travel_time`), and the crab burns
+     * `METABOLISM` energy for every tick spent travelling, once per reef it
+     * visits on the hunt.
      *
-     * ```text
-     *     there are no escaped prey yet
-     *     prey has not been caught
-     *     while prey can be caught
-     *       if prey escapes
-     *         mark the prey as escaped
-     *         try again
-     *     
-     *       if prey is not edible by this crab
-     *         mark the prey as escaped
-     *         try again
-     *       
-     *       prey has been caught
-     *       stop trying
-     *     
-     *     release each escaped prey back to its reef
-     *     was prey caught?
-     * ```
-     *
-     * Note: this pseudocode reads like a terrible poem.
+     * Returns true if the crab caught something. See `hunt_detailed` for the
+     * full story of the hunt.
      */
     pub fn hunt(&mut self) -> bool {
-        unimplemented!();
+        self.hunt_detailed().is_success()
+    }
+
+    /**
+     * Have this crab go hunting, exactly like `hunt`, and report what happened:
+     * what it caught and where, and which prey escaped or were inedible.
+     */
+    pub fn hunt_detailed(&mut self) -> HuntReport {
+        let mut report = HuntReport::new();
+        let mut escaped: Vec<(Box<dyn Prey>, usize)> = Vec::new();
+
+        while let Some((mut prey, reef_index)) = self.catch_prey(escaped.len() as u32) {
+            self.travel_to(reef_index, &mut report);
+            if prey.try_escape(self) {
+                report.record_escaped(prey.kind());
+                escaped.push((prey, reef_index));
+                continue;
+            }
+            if !self.diets.contains(prey.diet()) {
+                report.record_inedible(prey.kind());
+                escaped.push((prey, reef_index));
+                continue;
+            }
+
+            report.record_caught(prey.kind(), reef_index);
+            if self.energy >= Crab::MAX_ENERGY {
+                match self.pantry.add_prey(prey) {
+                    None => report.record_stored(),
+                    Some(prey) => self.eat(prey),
+                }
+            } else {
+                self.eat(prey);
+            }
+            self.strategy.record_catch(reef_index);
+            break;
+        }
+
+        for (prey, reef_index) in escaped {
+            self.release_prey(prey, reef_index);
+        }
+
+        self.hungry = !report.is_success();
+        report
     }
 
     /**
-     * Returns Some of any recipe from the given cookbook that matches the crab's diet
-     * preferences, or None if no such recipe exists.
+     * Returns Some recipe from the given cookbook that the crab can eat (it eats
+     * every diet the recipe requires, and the recipe requires at least one), or
+     * None if no such recipe exists.
      *
-     * IMPORTANT: you will need to add lifetime parameters to this function. It is
-     * up to you to figure out which ones and where. Do not make any other changes
-     * to the signature.
+     * The crab picks the recipe whose least-liked diet it weighs most; among
+     * equally weighted recipes, it picks the first in the cookbook.
+     */
+    pub fn choose_recipe<'a>(&self, cookbook: &'a Cookbook) -> Option<&'a Recipe> {
+        let weight = |r: &Recipe| r.diets().iter().map(|&d| self.diets.weight(d)).min();
+        // `max_by_key` returns the last of equal maximums, so search backwards.
+        cookbook
+            .recipes()
+            .rev()
+            .filter(|r| self.can_eat_recipe(r))
+            .max_by_key(|r| weight(r))
+    }
+
+    /**
+     * Returns every recipe from the given cookbook that the crab can eat, best
+     * first, with its score according to `scoring` (see `RecipeScoring`).
+     * Equally scored recipes keep their order in the cookbook.
+     */
+    pub fn choose_recipes<'a>(
+        &self,
+        cookbook: &'a Cookbook,
+        scoring: &RecipeScoring,
+    ) -> Vec<(&'a Recipe, f64)> {
+        let mut ranked: Vec<(&Recipe, f64)> = cookbook
+            .recipes()
+            .filter(|r| self.can_eat_recipe(r))
+            .map(|r| (r, self.score_recipe(r, scoring)))
+            .collect();
+        ranked.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        ranked
+    }
+
+    fn can_eat_recipe(&self, recipe: &Recipe) -> bool {
+        !recipe.diets().is_empty() && recipe.diets().iter().all(|&d| self.diets.contains(d))
+    }
+
+    fn score_recipe(&self, recipe: &Recipe, scoring: &RecipeScoring) -> f64 {
+        let diet = recipe
+            .diets()
+            .iter()
+            .map(|&d| self.diets.weight(d))
+            .min()
+            .unwrap_or(0);
+
+        let needed: u32 = recipe.ingredients().iter().map(|i| i.quantity()).sum();
+        let available: u32 = self.set_aside(recipe).iter().sum();
+        let ingredients = if needed == 0 {
+            1.0
+        } else {
+            f64::from(available) / f64::from(needed)
+        };
+
+        scoring.diet * f64::from(diet)
+            + scoring.ingredients * ingredients
+            + scoring.popularity * f64::from(recipe.popularity())
+    }
+
+    /**
+     * Returns true if this crab eats every diet the recipe requires, and has all
+     * of its ingredients in its pantry, fresh enough for the recipe.
+     */
+    pub fn can_cook(&self, recipe: &Recipe) -> bool {
+        self.check_recipe(recipe).is_ok()
+    }
+
+    fn check_recipe(&self, recipe: &Recipe) -> Result<(), OceanError> {
+        if !recipe.diets().iter().all(|&d| self.diets.contains(d)) {
+            return Err(OceanError::InedibleRecipe {
+                recipe: recipe.name().clone(),
+            });
+        }
+        for (i, available) in recipe.ingredients().iter().zip(self.set_aside(recipe)) {
+            if available < i.quantity() {
+                return Err(OceanError::MissingIngredient {
+                    prey: i.prey().clone(),
+                    needed: i.quantity(),
+                    available,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /**
+     * Returns how many prey in the pantry would go into each ingredient of the
+     * recipe (at most its quantity), taking the oldest prey first just as `cook`
+     * does. Each prey goes into one ingredient at most, even if several accept it.
+     */
+    fn set_aside(&self, recipe: &Recipe) -> Vec<u32> {
+        let mut used = vec![false; self.pantry.size()];
+        recipe
+            .ingredients()
+            .iter()
+            .map(|i| {
+                let mut taken = 0;
+                for (u, p) in used.iter_mut().zip(self.pantry.prey()) {
+                    if taken == i.quantity() {
+                        break;
+                    }
+                    if !*u && recipe.accepts(i, p) {
+                        *u = true;
+                        taken += 1;
+                    }
+                }
+                taken
+            })
+            .collect()
+    }
+
+    /**
+     * Cooks the given recipe, taking its ingredients out of this crab's pantry
+     * and eating them (see `eat`). Returns the energy the crab gained.
+     *
+     * Returns an error, leaving the pantry untouched, if the crab does not eat
+     * every diet the recipe requires or is missing some of its ingredients.
+     */
+    pub fn cook(&mut self, recipe: &Recipe) -> Result<u32, OceanError> {
+        self.check_recipe(recipe)?;
+
+        let before = self.energy;
+        for i in recipe.ingredients() {
+            for taken in 0..i.quantity() {
+                let prey = self.pantry.take_where(|p| recipe.accepts(i, p)).ok_or(
+                    OceanError::MissingIngredient {
+                        prey: i.prey().clone(),
+                        needed: i.quantity(),
+                        available: taken,
+                    },
+                )?;
+                self.eat(prey);
+            }
+        }
+        Ok(self.energy - before)
+    }
+
+    /**
+     * Cooks the best recipe (according to `choose_recipes`) that this crab has all
+     * the ingredients for, if any. Returns the recipe it cooked.
      */
-    pub fn choose_recipe(&self, cookbook: &Cookbook) -> Option<&Recipe> {
-        unimplemented!();
+    pub fn cook_best<'a>(
+        &mut self,
+        cookbook: &'a Cookbook,
+        scoring: &RecipeScoring,
+    ) -> Option<&'a Recipe> {
+        let recipe = self
+            .choose_recipes(cookbook, scoring)
+            .into_iter()
+            .map(|(r, _)| r)
+            .find(|r| self.can_cook(r))?;
+        self.cook(recipe).ok()?;
+        Some(recipe)
     }
 }
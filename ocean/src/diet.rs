@@ -1,7 +1,10 @@
-use crate::rand;
+use crate::rand::OceanRng;
+use std::collections::BTreeMap;
+use std::ops::BitOr;
 
 /// A crab's diet: fish, shellfish, or plants (seaweed, algae, etc).
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Diet {
     Fish,
     Shellfish,
@@ -9,11 +12,18 @@ pub enum Diet {
 }
 
 impl Diet {
-    pub fn random_diet() -> Diet {
+    /** Every diet, in order. */
+    pub const ALL: [Diet; 3] = [Diet::Fish, Diet::Shellfish, Diet::Plants];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn random_diet(rng: &mut OceanRng) -> Diet {
         // This brings the names in Diet into scope, so we can write
         // `Fish` rather than `Diet::Fish` (and so on) below.
         use Diet::*;
-        let r: u32 = rand::rand32() % 3;
+        let r: u32 = rng.below(3);
         match r {
             0 => Fish,
             1 => Shellfish,
@@ -22,3 +32,153 @@ impl Diet {
         }
     }
 }
+
+/**
+ * A set of diets, each with a preference weight. A crab eats any prey whose diet
+ * is in its set, and prefers recipes for the diets it weighs most.
+ *
+ * Like bitflags, sets can be built by or-ing diets together:
+ *
+ * ```
+ * use ocean::diet::{Diet, DietSet};
+ *
+ * let diets = Diet::Shellfish | Diet::Plants;
+ * assert!(diets.contains(Diet::Plants));
+ * assert!(!diets.contains(Diet::Fish));
+ * ```
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "BTreeMap<Diet, u32>", into = "BTreeMap<Diet, u32>")
+)]
+pub struct DietSet {
+    /** The weight of each diet, indexed by `Diet::index`. Zero means not in the set. */
+    weights: [u32; 3],
+}
+
+impl DietSet {
+    pub fn empty() -> DietSet {
+        DietSet { weights: [0; 3] }
+    }
+
+    /**
+     * Returns the set containing only the given diet, with weight 1.
+     */
+    pub fn only(diet: Diet) -> DietSet {
+        DietSet::empty().with(diet, 1)
+    }
+
+    /**
+     * Returns the set of every diet, all weighted equally.
+     */
+    pub fn omnivore() -> DietSet {
+        DietSet { weights: [1; 3] }
+    }
+
+    /**
+     * Returns this set with the given diet weighted by `weight`. A weight of zero
+     * removes the diet from the set.
+     */
+    pub fn with(mut self, diet: Diet, weight: u32) -> DietSet {
+        self.weights[diet.index()] = weight;
+        self
+    }
+
+    pub fn contains(&self, diet: Diet) -> bool {
+        self.weight(diet) > 0
+    }
+
+    /**
+     * Returns how strongly this set prefers the given diet, or zero if it is not in the set.
+     */
+    pub fn weight(&self, diet: Diet) -> u32 {
+        self.weights[diet.index()]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits() == 0
+    }
+
+    /**
+     * Returns the diets in this set, in `Diet::ALL` order.
+     */
+    pub fn diets(&self) -> impl Iterator<Item = Diet> + '_ {
+        Diet::ALL.into_iter().filter(|&d| self.contains(d))
+    }
+
+    /**
+     * Returns the set as bitflags: bit `i` is set if `Diet::ALL[i]` is in the set.
+     */
+    pub fn bits(&self) -> u8 {
+        self.diets().fold(0, |bits, d| bits | 1 << d.index())
+    }
+
+    /**
+     * Returns the diets in both sets, each with the smaller of its two weights.
+     */
+    pub fn intersection(&self, other: &DietSet) -> DietSet {
+        let mut set = DietSet::empty();
+        for d in Diet::ALL {
+            set.weights[d.index()] = self.weight(d).min(other.weight(d));
+        }
+        set
+    }
+
+    /**
+     * Returns the diets in either set, each with the larger of its two weights.
+     */
+    pub fn union(&self, other: &DietSet) -> DietSet {
+        let mut set = DietSet::empty();
+        for d in Diet::ALL {
+            set.weights[d.index()] = self.weight(d).max(other.weight(d));
+        }
+        set
+    }
+}
+
+impl From<Diet> for DietSet {
+    fn from(diet: Diet) -> Self {
+        DietSet::only(diet)
+    }
+}
+
+impl From<BTreeMap<Diet, u32>> for DietSet {
+    fn from(weights: BTreeMap<Diet, u32>) -> Self {
+        weights
+            .into_iter()
+            .fold(DietSet::empty(), |set, (d, w)| set.with(d, w))
+    }
+}
+
+impl From<DietSet> for BTreeMap<Diet, u32> {
+    fn from(set: DietSet) -> Self {
+        set.diets().map(|d| (d, set.weight(d))).collect()
+    }
+}
+
+impl BitOr for Diet {
+    type Output = DietSet;
+
+    fn bitor(self, rhs: Diet) -> DietSet {
+        DietSet::only(self) | rhs
+    }
+}
+
+impl BitOr<Diet> for DietSet {
+    type Output = DietSet;
+
+    /** Adds the diet to the set with weight 1, unless it is already in the set. */
+    fn bitor(self, rhs: Diet) -> DietSet {
+        self.union(&DietSet::only(rhs))
+    }
+}
+
+impl BitOr for DietSet {
+    type Output = DietSet;
+
+    fn bitor(self, rhs: DietSet) -> DietSet {
+        self.union(&rhs)
+    }
+}
@@ -0,0 +1,36 @@
+use crate::rand::OceanRng;
+
+/**
+ * A distribution of whole numbers to sample parameters (such as a minnow's speed) from.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Distribution {
+    /** Always the same value. */
+    Fixed(u32),
+    /** Any value from `min` to `max` (inclusive), all equally likely. */
+    Uniform { min: u32, max: u32 },
+    /** A normal distribution, rounded to the nearest whole number and clamped at zero. */
+    Normal { mean: f64, std_dev: f64 },
+}
+
+impl Distribution {
+    pub fn sample(&self, rng: &mut OceanRng) -> u32 {
+        use Distribution::*;
+        match *self {
+            Fixed(value) => value,
+            Uniform { min, max } => {
+                let (lo, hi) = (min.min(max), min.max(max));
+                let span = u64::from(hi - lo) + 1;
+                lo + (rng.next_u64() % span) as u32
+            }
+            Normal { mean, std_dev } => {
+                // Box-Muller transform. 1 - u1 is in (0, 1], so its log is finite.
+                let u1 = 1.0 - rng.next_f64();
+                let u2 = rng.next_f64();
+                let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+                (mean + std_dev * z).round().clamp(0.0, u32::MAX as f64) as u32
+            }
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use crate::crab::{Crab, CrabId};
+use crate::prey::PreyKind;
+use std::fmt;
+
+/**
+ * The ways an operation on the ocean (or one of its beaches) can fail.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OceanError {
+    /** There is no crab at `index` on a beach holding `size` crabs. */
+    UnknownCrab { index: usize, size: usize },
+    /** There is no beach at `index` in an ocean of `count` beaches. */
+    UnknownBeach { index: usize, count: usize },
+    /** There is no crab with this id on the beach. */
+    MissingCrab { id: CrabId },
+    /** A different crab with the same id already lives on (or has lived on) the beach. */
+    DuplicateCrabId { id: CrabId },
+    /** A crab cannot breed with itself. */
+    SelfBreeding { index: usize },
+    /** The beach already holds as many crabs as it can. */
+    CapacityExceeded { capacity: usize },
+    /** Crabs on a beach of `size` crabs failed to breed because of crowding. */
+    CrowdedOut { size: usize },
+    /** No prey with this tag is registered in the `PreyRegistry`. */
+    UnknownPrey { tag: String },
+    /** The registered constructor for this tag rejected the parameters it was given. */
+    InvalidPrey { tag: String },
+    /** The crab does not eat every diet the named recipe requires. */
+    InedibleRecipe { recipe: String },
+    /** The crab's pantry holds fewer of this prey (fresh enough) than a recipe calls for. */
+    MissingIngredient {
+        prey: PreyKind,
+        needed: u32,
+        available: u32,
+    },
+    /** The reef is `distance` away from the crab's home, beyond the crab's `range`. */
+    OutOfRange { distance: u32, range: u32 },
+}
+
+impl fmt::Display for OceanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OceanError::UnknownCrab { index, size } => {
+                write!(f, "no crab at index {} on a beach of {} crabs", index, size)
+            }
+            OceanError::UnknownBeach { index, count } => {
+                write!(
+                    f,
+                    "no beach at index {} in an ocean of {} beaches",
+                    index, count
+                )
+            }
+            OceanError::MissingCrab { id } => write!(f, "no crab with id {} on the beach", id),
+            OceanError::DuplicateCrabId { id } => {
+                write!(f, "a different crab with id {} is already on the beach", id)
+            }
+            OceanError::SelfBreeding { index } => {
+                write!(f, "crab at index {} cannot breed with itself", index)
+            }
+            OceanError::CapacityExceeded { capacity } => {
+                write!(f, "beach is already at its capacity of {} crabs", capacity)
+            }
+            OceanError::CrowdedOut { size } => {
+                write!(f, "too crowded to breed on a beach of {} crabs", size)
+            }
+            OceanError::UnknownPrey { tag } => write!(f, "no prey registered as {}", tag),
+            OceanError::InvalidPrey { tag } => write!(f, "invalid parameters for prey {}", tag),
+            OceanError::InedibleRecipe { recipe } => {
+                write!(f, "crab does not eat everything in {}", recipe)
+            }
+            OceanError::MissingIngredient {
+                prey,
+                needed,
+                available,
+            } => write!(
+                f,
+                "recipe needs {} {} but the pantry only has {}",
+                needed, prey, available
+            ),
+            OceanError::OutOfRange { distance, range } => write!(
+                f,
+                "reef is {} away, but the crab can only travel {}",
+                distance, range
+            ),
+        }
+    }
+}
+
+impl std::error::Error for OceanError {}
+
+/**
+ * A crab that `Beach::try_add_crab` turned away, handed back along with why, so
+ * that it is not lost.
+ */
+#[derive(Debug)]
+pub struct AddCrabError {
+    pub error: OceanError,
+    pub crab: Box<Crab>,
+}
+
+impl fmt::Display for AddCrabError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cannot add {}: {}", self.crab.name(), self.error)
+    }
+}
+
+impl std::error::Error for AddCrabError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
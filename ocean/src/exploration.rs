@@ -0,0 +1,98 @@
+use crate::beach::Beach;
+use crate::rand::OceanRng;
+use crate::report::TickReport;
+use crate::shared::{self, SharedReef};
+
+/**
+ * How crabs find (and lose track of) reefs on their own during `Ocean::step`,
+ * instead of only knowing the reefs they are given with `Crab::discover_reef`.
+ *
+ * Each tick, a crab:
+ *   - forgets every reef it has seen empty for `forget_after` ticks in a row
+ *     (see `Crab::forget_empty_reefs`),
+ *   - has a `discovery` chance of discovering each reef of the ocean within its
+ *     range that it does not know of yet (see `Crab::can_reach`), as long as that
+ *     reef has prey left, and
+ *   - has a `sharing` chance of learning of each reef within its range that
+ *     another crab on its beach knows of, as long as that reef has prey left.
+ *
+ * Each chance is a probability between 0 and 1.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExplorationRates {
+    pub discovery: f64,
+    pub sharing: f64,
+    /** Zero means crabs never forget a reef. */
+    pub forget_after: u32,
+}
+
+impl ExplorationRates {
+    /**
+     * Rates under which crabs never discover, share or forget any reefs.
+     */
+    pub fn none() -> ExplorationRates {
+        ExplorationRates {
+            discovery: 0.0,
+            sharing: 0.0,
+            forget_after: 0,
+        }
+    }
+}
+
+impl Default for ExplorationRates {
+    fn default() -> Self {
+        ExplorationRates {
+            discovery: 0.1,
+            sharing: 0.25,
+            forget_after: 5,
+        }
+    }
+}
+
+/**
+ * The exploration phase of a tick for the crabs on one beach, looking for
+ * reefs among `reefs` (see `ExplorationRates`).
+ */
+pub(crate) fn explore(
+    beach: &mut Beach,
+    reefs: &[SharedReef],
+    rates: &ExplorationRates,
+    rng: &mut OceanRng,
+    report: &mut TickReport,
+) {
+    for crab in beach.crabs_mut() {
+        report.record_reefs_forgotten(crab.forget_empty_reefs(rates.forget_after));
+    }
+
+    // What the beach knew at the start of the tick: crabs only pass on what they
+    // learned this tick on the next one.
+    let mut known: Vec<SharedReef> = Vec::new();
+    for reef in beach.crabs().flat_map(|c| c.reefs()) {
+        if shared::read(reef).population() > 0 && !known.iter().any(|k| shared::ptr_eq(k, reef)) {
+            known.push(SharedReef::clone(reef));
+        }
+    }
+
+    for crab in beach.crabs_mut() {
+        let candidates = reefs
+            .iter()
+            .map(|r| (r, rates.discovery))
+            .chain(known.iter().map(|r| (r, rates.sharing)));
+        for (reef, chance) in candidates {
+            // Skipping empty reefs also keeps a crab from rediscovering a reef it
+            // forgot above.
+            let worth_finding = {
+                let r = shared::read(reef);
+                r.population() > 0 && crab.can_reach(&r)
+            };
+            if !worth_finding || crab.knows_reef(reef) {
+                continue;
+            }
+            if rng.chance(chance) {
+                crab.discover_reef(SharedReef::clone(reef));
+                report.record_reef_discovered();
+            }
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use crate::color::Color;
+use crate::diet::Diet;
+use crate::rand::OceanRng;
+
+/**
+ * The heritable traits of a crab: the speed it grows up to, its color, and its diet.
+ */
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Genome {
+    speed: u32,
+    color: Color,
+    diet: Diet,
+}
+
+/**
+ * The probability of each gene mutating when a new genome is produced by `Genome::crossover`.
+ *
+ * Each rate is a probability between 0 and 1.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MutationRates {
+    pub speed: f64,
+    pub color: f64,
+    pub diet: f64,
+}
+
+impl MutationRates {
+    /**
+     * Rates under which offspring are always a pure crossover of their parents.
+     */
+    pub fn none() -> MutationRates {
+        MutationRates {
+            speed: 0.0,
+            color: 0.0,
+            diet: 0.0,
+        }
+    }
+}
+
+impl Default for MutationRates {
+    fn default() -> Self {
+        MutationRates {
+            speed: 0.05,
+            color: 0.0,
+            diet: 0.05,
+        }
+    }
+}
+
+impl Genome {
+    pub fn new(speed: u32, color: Color, diet: Diet) -> Genome {
+        Genome { speed, color, diet }
+    }
+
+    /** The speed a crab with this genome grows up to. */
+    pub fn speed(&self) -> u32 {
+        self.speed
+    }
+
+    pub fn color(&self) -> &Color {
+        &self.color
+    }
+
+    pub fn diet(&self) -> Diet {
+        self.diet
+    }
+
+    /**
+     * Produces a child genome from two parent genomes.
+     *
+     *   - Speed and diet are each inherited from one parent, chosen at random.
+     *   - Color is the cross of both parents' colors (see `Color::cross`).
+     *
+     * Then each gene mutates with the probability given in `rates`:
+     *   - Speed goes up or down by one (but never below one).
+     *   - One color component is replaced with a random value.
+     *   - Diet is replaced with `Diet::random_diet`.
+     */
+    pub fn crossover(
+        g1: &Genome,
+        g2: &Genome,
+        rates: &MutationRates,
+        rng: &mut OceanRng,
+    ) -> Genome {
+        let mut speed = if rng.chance(0.5) { g1.speed } else { g2.speed };
+        let mut color = Color::cross(&g1.color, &g2.color);
+        let mut diet = if rng.chance(0.5) { g1.diet } else { g2.diet };
+
+        if rng.chance(rates.speed) {
+            speed = if rng.chance(0.5) {
+                speed.saturating_add(1)
+            } else {
+                speed.saturating_sub(1).max(1)
+            };
+        }
+
+        if rng.chance(rates.color) {
+            let value = rng.below(256) as u8;
+            match rng.below(3) {
+                0 => color.r = value,
+                1 => color.g = value,
+                2 => color.b = value,
+                _ => unreachable!(),
+            }
+        }
+
+        if rng.chance(rates.diet) {
+            diet = Diet::random_diet(rng);
+        }
+
+        Genome { speed, color, diet }
+    }
+}
@@ -0,0 +1,51 @@
+use std::fmt;
+
+/**
+ * A point on the ocean's grid, where beaches and reefs are placed.
+ *
+ * Everything is placed at the origin unless given a position, so an ocean
+ * without geography behaves as if everything were right next to each other.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Position {
+    pub fn new(x: i32, y: i32) -> Position {
+        Position { x, y }
+    }
+
+    /**
+     * Returns the straight-line distance between two positions.
+     */
+    pub fn distance(&self, other: &Position) -> f64 {
+        let dx = f64::from(self.x) - f64::from(other.x);
+        let dy = f64::from(self.y) - f64::from(other.y);
+        dx.hypot(dy)
+    }
+
+    /**
+     * Returns the number of ticks it takes something moving at `speed` to get
+     * from this position to `other`, rounded up. Something with no speed never
+     * gets anywhere (but is already where it is).
+     */
+    pub fn travel_time(&self, other: &Position, speed: u32) -> u32 {
+        let distance = self.distance(other);
+        if distance == 0.0 {
+            0
+        } else if speed == 0 {
+            u32::MAX
+        } else {
+            (distance / f64::from(speed)).ceil() as u32
+        }
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
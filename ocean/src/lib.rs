@@ -3,7 +3,23 @@ pub mod color;
 pub mod cookbook;
 pub mod crab;
 pub mod diet;
+pub mod distribution;
+pub mod error;
+pub mod exploration;
+pub mod genome;
+pub mod grid;
+pub mod migration;
 pub mod ocean;
+pub mod pantry;
+pub mod pedigree;
 pub mod prey;
 pub mod rand;
 pub mod reef;
+pub mod registry;
+pub mod report;
+pub mod scenario;
+pub mod shared;
+#[cfg(feature = "serde")]
+pub mod snapshot;
+pub mod strategy;
+pub mod sweep;
@@ -0,0 +1,133 @@
+use crate::beach::{Beach, Overflow};
+use crate::ocean::Ocean;
+use crate::report::TickReport;
+
+/**
+ * When crabs leave their beach for another during `Ocean::step`.
+ *
+ * Each tick, after hunting, a crab leaves:
+ *   - with a `crowding` chance, if its beach is overcrowded: it holds at least
+ *     `crowded_at` of its capacity (beaches without a capacity are never
+ *     overcrowded), or
+ *   - with a `scarcity` chance, if it came back hungry from its last hunt.
+ *
+ * A crab that leaves moves to the nearest other beach that has room and is not
+ * overcrowded (ties go to the first such beach), keeping the reefs it knows of
+ * (see `Ocean::migrate_crab`). If there is no such beach, it stays where it is.
+ *
+ * Each chance is a probability between 0 and 1.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MigrationPolicy {
+    pub crowded_at: f64,
+    pub crowding: f64,
+    pub scarcity: f64,
+}
+
+impl MigrationPolicy {
+    /**
+     * A policy under which crabs never leave their beach.
+     */
+    pub fn none() -> MigrationPolicy {
+        MigrationPolicy {
+            crowded_at: 1.0,
+            crowding: 0.0,
+            scarcity: 0.0,
+        }
+    }
+
+    /**
+     * Returns true if the given beach is overcrowded under this policy.
+     */
+    pub fn is_crowded(&self, beach: &Beach) -> bool {
+        beach
+            .capacity()
+            .is_some_and(|c| beach.size() as f64 >= self.crowded_at * c as f64)
+    }
+}
+
+impl Default for MigrationPolicy {
+    fn default() -> Self {
+        MigrationPolicy {
+            crowded_at: 0.9,
+            crowding: 0.2,
+            scarcity: 0.1,
+        }
+    }
+}
+
+/**
+ * The migration phase of a tick (see `MigrationPolicy`). Every crab decides
+ * whether to leave before any crab moves.
+ */
+pub(crate) fn migrate(ocean: &mut Ocean, policy: &MigrationPolicy, report: &mut TickReport) {
+    let mut leaving = Vec::new();
+    for (from, beach) in ocean.beaches.iter().enumerate() {
+        let crowded = policy.is_crowded(beach);
+        for crab in beach.crabs() {
+            let rng = &mut ocean.rng;
+            if (crowded && rng.chance(policy.crowding))
+                || (crab.is_hungry() && rng.chance(policy.scarcity))
+            {
+                leaving.push((from, crab.id()));
+            }
+        }
+    }
+
+    for (from, id) in leaving {
+        let to = nearest_beach(&ocean.beaches, from, |b| !policy.is_crowded(b));
+        if let Some(to) = to {
+            if ocean.migrate_crab(from, id, to).is_ok() {
+                report.record_migrated();
+            }
+        }
+    }
+}
+
+/**
+ * Moves the newest crabs beyond the capacity of every beach that lets overflow
+ * migrate (see `Overflow::Migrate`) to the nearest beaches with room, evicting
+ * the ones with nowhere to go.
+ */
+pub(crate) fn relocate_overflow(ocean: &mut Ocean, report: &mut TickReport) {
+    for from in 0..ocean.beaches.len() {
+        let beach = &ocean.beaches[from];
+        let capacity = match beach.capacity() {
+            Some(capacity) if beach.overflow() == Overflow::Migrate => capacity,
+            _ => continue,
+        };
+        let surplus: Vec<_> = beach.crabs().skip(capacity).map(|c| c.id()).collect();
+
+        for id in surplus {
+            let moved = nearest_beach(&ocean.beaches, from, |_| true)
+                .is_some_and(|to| ocean.migrate_crab(from, id, to).is_ok());
+            if moved {
+                report.record_migrated();
+            } else if ocean.beaches[from].remove_crab_by_id(id).is_ok() {
+                report.record_evicted(1);
+            }
+        }
+    }
+}
+
+/**
+ * Returns the index of the nearest beach to the beach at `from` that has room
+ * and is `acceptable`, if any. Ties go to the first such beach.
+ */
+fn nearest_beach(
+    beaches: &[Beach],
+    from: usize,
+    acceptable: impl Fn(&Beach) -> bool,
+) -> Option<usize> {
+    let home = beaches[from].position();
+    beaches
+        .iter()
+        .enumerate()
+        .filter(|&(i, b)| i != from && !b.is_full() && acceptable(b))
+        .min_by(|(_, a), (_, b)| {
+            home.distance(&a.position())
+                .total_cmp(&home.distance(&b.position()))
+        })
+        .map(|(i, _)| i)
+}
@@ -1,36 +1,280 @@
 use crate::beach::Beach;
-use crate::prey::{Algae, Clam, Minnow, Shrimp};
-use crate::reef::Reef;
-use std::cell::RefCell;
-use std::rc::Rc;
-use std::slice::Iter;
+use crate::cookbook::{Cookbook, RecipeScoring};
+use crate::crab::{Crab, CrabId, CrabIds, DeathCause};
+use crate::distribution::Distribution;
+use crate::error::OceanError;
+use crate::exploration::{self, ExplorationRates};
+use crate::grid::Position;
+use crate::migration::{self, MigrationPolicy};
+use crate::prey::{Algae, Clam, Minnow, PreyParams, Shrimp};
+use crate::rand::OceanRng;
+use crate::reef::{Reef, ReefSpec};
+use crate::registry::{PreyRegistry, StrategyRegistry};
+use crate::report::TickReport;
+use crate::shared::{self, SharedReef};
+use std::slice::{Iter, IterMut};
 
 #[derive(Debug)]
 pub struct Ocean {
-    // TODO: Fill in fields here.
+    pub(crate) beaches: Vec<Beach>,
+    pub(crate) reefs: Vec<SharedReef>,
+    pub(crate) tick: u64,
+    pub(crate) rng: OceanRng,
+    pub(crate) crab_ids: CrabIds,
+    prey_params: PreyParams,
+    registry: PreyRegistry,
+    strategy_registry: StrategyRegistry,
+    pub(crate) cookbook: Option<Cookbook>,
+    exploration: Option<ExplorationRates>,
+    migration: Option<MigrationPolicy>,
+    lifespan: Option<Distribution>,
 }
 
 impl Ocean {
     pub fn new() -> Ocean {
-        unimplemented!();
+        Ocean::with_seed(0)
     }
 
-    pub fn add_beach(&mut self, beach: Beach) {
-        unimplemented!();
+    /**
+     * Creates an empty ocean whose randomness is driven entirely by `seed`.
+     */
+    pub fn with_seed(seed: u64) -> Ocean {
+        Ocean {
+            beaches: Vec::new(),
+            reefs: Vec::new(),
+            tick: 0,
+            rng: OceanRng::new(seed),
+            crab_ids: CrabIds::new(),
+            prey_params: PreyParams::default(),
+            registry: PreyRegistry::new(),
+            strategy_registry: StrategyRegistry::new(),
+            cookbook: None,
+            exploration: None,
+            migration: None,
+            lifespan: None,
+        }
+    }
+
+    /**
+     * Returns the cookbook crabs cook from during `step`, if any.
+     */
+    pub fn cookbook(&self) -> Option<&Cookbook> {
+        self.cookbook.as_ref()
+    }
+
+    /**
+     * Sets the cookbook crabs cook from during `step`. With no cookbook, crabs never cook.
+     */
+    pub fn set_cookbook(&mut self, cookbook: Option<Cookbook>) {
+        self.cookbook = cookbook;
+    }
+
+    /**
+     * Returns the rates at which crabs explore the ocean during `step`, if they do.
+     */
+    pub fn exploration(&self) -> Option<&ExplorationRates> {
+        self.exploration.as_ref()
+    }
+
+    /**
+     * Sets the rates at which crabs explore the ocean during `step`. Without
+     * them, crabs only ever know the reefs they are given.
+     */
+    pub fn set_exploration(&mut self, rates: Option<ExplorationRates>) {
+        self.exploration = rates;
+    }
+
+    /**
+     * Returns the policy under which crabs migrate between beaches during `step`, if they do.
+     */
+    pub fn migration(&self) -> Option<&MigrationPolicy> {
+        self.migration.as_ref()
+    }
+
+    /**
+     * Sets the policy under which crabs migrate between beaches during `step`.
+     * Without one, crabs stay on their beach for life.
+     */
+    pub fn set_migration(&mut self, policy: Option<MigrationPolicy>) {
+        self.migration = policy;
+    }
+
+    /**
+     * Returns the distribution crabs' lifespans are drawn from during `step`, if any.
+     */
+    pub fn lifespan(&self) -> Option<&Distribution> {
+        self.lifespan.as_ref()
+    }
+
+    /**
+     * Sets the distribution crabs' lifespans are drawn from. Every crab without
+     * a lifespan (see `Crab::lifespan`) is given one at the start of its first
+     * tick in the ocean. Without a distribution, such crabs live forever.
+     */
+    pub fn set_lifespan(&mut self, lifespan: Option<Distribution>) {
+        self.lifespan = lifespan;
     }
 
-    pub fn beaches(&self) -> Iter<Beach> {
-        unimplemented!();
+    /**
+     * Returns the registry of prey that `generate_reef_from` can generate.
+     */
+    pub fn registry(&self) -> &PreyRegistry {
+        &self.registry
     }
 
-    pub fn reefs(&self) -> Iter<Rc<RefCell<Reef>>> {
-        unimplemented!();
+    /**
+     * Returns the registry of prey that `generate_reef_from` can generate, so
+     * that custom prey can be registered.
+     */
+    pub fn registry_mut(&mut self) -> &mut PreyRegistry {
+        &mut self.registry
+    }
+
+    /**
+     * Returns the registry of hunting strategies that can be restored when this
+     * ocean is saved and loaded again (see `to_json`).
+     */
+    pub fn strategy_registry(&self) -> &StrategyRegistry {
+        &self.strategy_registry
+    }
+
+    /**
+     * Returns the registry of hunting strategies that can be restored when this
+     * ocean is saved and loaded again, so that custom strategies can be registered.
+     */
+    pub fn strategy_registry_mut(&mut self) -> &mut StrategyRegistry {
+        &mut self.strategy_registry
+    }
+
+    pub fn prey_params(&self) -> &PreyParams {
+        &self.prey_params
+    }
+
+    /**
+     * Sets the parameters of prey created by `generate_reef` from now on.
+     */
+    pub fn set_prey_params(&mut self, params: PreyParams) {
+        self.prey_params = params;
+    }
+
+    pub fn rng(&self) -> &OceanRng {
+        &self.rng
+    }
+
+    pub fn rng_mut(&mut self) -> &mut OceanRng {
+        &mut self.rng
+    }
+
+    /**
+     * Adds a beach to this ocean.
+     *
+     * The beach's random number generator is replaced with one forked from the
+     * ocean's, so that the beach's randomness is reproducible from the ocean's seed.
+     * From then on, the ocean hands out the ids of crabs added to the beach.
+     * Crabs already on the beach keep their ids, so to keep ids unique across
+     * beaches, add crabs to beaches made with `new_beach`.
+     */
+    pub fn add_beach(&mut self, mut beach: Beach) {
+        beach.set_rng(self.rng.fork());
+        beach.share_crab_ids(&self.crab_ids);
+        self.beaches.push(beach);
+    }
+
+    /**
+     * Creates an empty beach that takes crab ids from this ocean, so that its
+     * crabs never share an id with a crab on any other beach in the ocean.
+     * The beach still has to be added with `add_beach`.
+     */
+    pub fn new_beach(&self) -> Beach {
+        let mut beach = Beach::new();
+        beach.crab_ids = self.crab_ids.clone();
+        beach
+    }
+
+    /**
+     * Moves the crab with the given id from the beach at index `from` to the
+     * beach at index `to`. The crab keeps every reef it knows of, makes its new
+     * beach its home, and brings its ancestry along into the new beach's
+     * pedigree.
+     *
+     * Returns an error if either beach does not exist, the crab is not on the
+     * `from` beach, the `to` beach is full, or a different crab with the same id
+     * as the crab (or one of its ancestors) lives or has lived on the `to` beach.
+     * Moving a crab to the beach it is already on does nothing.
+     */
+    pub fn migrate_crab(&mut self, from: usize, id: CrabId, to: usize) -> Result<(), OceanError> {
+        let count = self.beaches.len();
+        for index in [from, to] {
+            if index >= count {
+                return Err(OceanError::UnknownBeach { index, count });
+            }
+        }
+        let crab = self.beaches[from]
+            .find_crab(id)
+            .ok_or(OceanError::MissingCrab { id })?;
+        if from == to {
+            return Ok(());
+        }
+        let destination = &self.beaches[to];
+        destination.check_capacity()?;
+        destination.check_crab_id(crab)?;
+        if let Some(id) = destination
+            .pedigree
+            .conflict(&self.beaches[from].pedigree, id)
+        {
+            return Err(OceanError::DuplicateCrabId { id });
+        }
+
+        let crab = self.beaches[from].remove_crab_by_id(id)?;
+        let (source, destination) = if from < to {
+            let (left, right) = self.beaches.split_at_mut(to);
+            (&left[from], &mut right[0])
+        } else {
+            let (left, right) = self.beaches.split_at_mut(from);
+            (&right[0], &mut left[to])
+        };
+        destination.pedigree.record_ancestry(&source.pedigree, id);
+        destination.add_crab(crab);
+        Ok(())
+    }
+
+    pub fn beaches(&self) -> Iter<'_, Beach> {
+        self.beaches.iter()
+    }
+
+    pub fn beaches_mut(&mut self) -> IterMut<'_, Beach> {
+        self.beaches.iter_mut()
+    }
+
+    pub fn reefs(&self) -> Iter<'_, SharedReef> {
+        self.reefs.iter()
+    }
+
+    /**
+     * Returns the reefs no further than `range` from the given position, such
+     * as the reefs a crab could discover from its home (see `Crab::range`).
+     */
+    pub fn reefs_within(
+        &self,
+        position: Position,
+        range: u32,
+    ) -> impl Iterator<Item = &SharedReef> {
+        self.reefs
+            .iter()
+            .filter(move |r| shared::read(r).position().distance(&position) <= f64::from(range))
+    }
+
+    /**
+     * Returns the number of ticks this ocean has been simulated for.
+     */
+    pub fn tick(&self) -> u64 {
+        self.tick
     }
 
     /**
      * Generate a reef with the specified number of each concrete type of prey, and then add it to the ocean.
-     *   - Minnows should have a speed of 25.
-     *   - Shrimp should have an energy of 1.
+     *   - Minnows have a speed of `prey_params().minnow_speed` (25 by default).
+     *   - Shrimp have an energy of `prey_params().shrimp_energy` (1 by default).
      *
      * Returns a reference to the newly created reef.
      */
@@ -40,7 +284,222 @@ impl Ocean {
         n_shrimp: u32,
         n_clams: u32,
         n_algae: u32,
-    ) -> Rc<RefCell<Reef>> {
-        unimplemented!();
+    ) -> SharedReef {
+        let mut reef = Reef::new();
+        for _ in 0..n_minnows {
+            reef.add_prey(Box::new(Minnow::new(self.prey_params.minnow_speed)));
+        }
+        for _ in 0..n_shrimp {
+            reef.add_prey(Box::new(Shrimp::new(self.prey_params.shrimp_energy)));
+        }
+        for _ in 0..n_clams {
+            reef.add_prey(Box::new(Clam::new()));
+        }
+        for _ in 0..n_algae {
+            reef.add_prey(Box::new(Algae::new()));
+        }
+
+        let reef = shared::share(reef);
+        self.reefs.push(SharedReef::clone(&reef));
+        reef
+    }
+
+    /**
+     * Generates a reef from the given spec, sampling prey parameters from this
+     * ocean's random number generator, and adds it to the ocean.
+     *
+     * Returns a reference to the newly created reef, or an error if the spec
+     * contains prey that are not in this ocean's `registry`.
+     */
+    pub fn generate_reef_from(&mut self, spec: &ReefSpec) -> Result<SharedReef, OceanError> {
+        let reef = shared::share(spec.build(&self.registry, &mut self.rng)?);
+        self.reefs.push(SharedReef::clone(&reef));
+        Ok(reef)
+    }
+
+    /**
+     * Advances the simulation by a single tick.
+     *
+     * If the ocean has a lifespan distribution, crabs without a lifespan are
+     * first given one (see `set_lifespan`).
+     *
+     * If the ocean has exploration rates, crabs then forget long-empty reefs,
+     * discover reefs nearby and learn of reefs from their neighbors (see
+     * `ExplorationRates`), one beach at a time.
+     *
+     * Every crab on every beach goes hunting once in the reefs it has discovered,
+     * in beach order and then in the order the crabs live on their beach. Crabs
+     * that catch nothing are left hungry until their next successful hunt.
+     *
+     * Crabs that catch nothing eat from their pantries instead, if they can (see
+     * `Crab::eat_from_pantry`). Then crabs with room for more energy cook the
+     * best recipe they have all the ingredients for in their pantries, if the
+     * ocean has a cookbook (see `Crab::cook_best`).
+     *
+     * Then every crab burns `Crab::METABOLISM` energy, grows a tick older and
+     * grows a little (see `Crab::grow`), and the prey in its pantry age (see
+     * `Pantry::age`). Crabs that have run out of energy or reached the end of
+     * their lifespan die and are removed from their beach (see `Beach::remove_dead`).
+     *
+     * If the ocean has a migration policy, some crabs then leave their beach
+     * for another (see `MigrationPolicy`). Crabs beyond the capacity of beaches
+     * that let overflow migrate move to other beaches, or are evicted (see
+     * `Overflow::Migrate`).
+     *
+     * Finally, the prey in every reef get a chance to reproduce (see `Reef::regenerate`).
+     *
+     * With the `sync` feature, beaches whose crabs share no reefs with each other
+     * are processed in parallel. This gives exactly the same results as processing
+     * them one after another.
+     */
+    pub fn step(&mut self) -> TickReport {
+        self.tick += 1;
+        let mut report = TickReport::new(self.tick);
+
+        if let Some(lifespan) = &self.lifespan {
+            for crab in self.beaches.iter_mut().flat_map(|b| b.crabs_mut()) {
+                if crab.lifespan().is_none() {
+                    crab.set_lifespan(Some(lifespan.sample(&mut self.rng)));
+                }
+            }
+        }
+        if let Some(rates) = self.exploration {
+            for beach in self.beaches.iter_mut() {
+                exploration::explore(beach, &self.reefs, &rates, &mut self.rng, &mut report);
+            }
+        }
+        self.live(&mut report);
+        if let Some(policy) = self.migration {
+            migration::migrate(self, &policy, &mut report);
+        }
+        migration::relocate_overflow(self, &mut report);
+
+        for reef in &self.reefs {
+            report.record_prey_born(shared::write(reef).regenerate(&mut self.rng));
+        }
+        report.set_prey_remaining(
+            self.reefs
+                .iter()
+                .map(|r| shared::read(r).population())
+                .sum(),
+        );
+        report
+    }
+
+    #[cfg(not(feature = "sync"))]
+    fn live(&mut self, report: &mut TickReport) {
+        for beach in self.beaches.iter_mut() {
+            live(beach, self.cookbook.as_ref(), report);
+        }
+    }
+
+    #[cfg(feature = "sync")]
+    fn live(&mut self, report: &mut TickReport) {
+        use rayon::prelude::*;
+
+        let mut beaches: Vec<Option<&mut Beach>> = self.beaches.iter_mut().map(Some).collect();
+        let groups: Vec<Vec<&mut Beach>> = independent_groups(&beaches)
+            .into_iter()
+            .map(|group| {
+                group
+                    .into_iter()
+                    .map(|i| beaches[i].take().unwrap())
+                    .collect()
+            })
+            .collect();
+
+        let tick = self.tick;
+        let cookbook = self.cookbook.as_ref();
+        let partials: Vec<TickReport> = groups
+            .into_par_iter()
+            .map(|group| {
+                let mut partial = TickReport::new(tick);
+                for beach in group {
+                    live(beach, cookbook, &mut partial);
+                }
+                partial
+            })
+            .collect();
+
+        for partial in &partials {
+            report.merge(partial);
+        }
+    }
+
+    /**
+     * Advances the simulation by `n_ticks` ticks, returning the report for each tick in order.
+     */
+    pub fn run(&mut self, n_ticks: u64) -> Vec<TickReport> {
+        (0..n_ticks).map(|_| self.step()).collect()
+    }
+}
+
+/**
+ * One tick in the life of the crabs on a beach: hunting, eating, cooking,
+ * metabolism, growth and starvation.
+ */
+fn live(beach: &mut Beach, cookbook: Option<&Cookbook>, report: &mut TickReport) {
+    for crab in beach.crabs_mut() {
+        let caught = crab.hunt();
+        report.record_hunt(caught);
+        if !caught {
+            crab.eat_from_pantry();
+        }
+        if let Some(cookbook) = cookbook {
+            if crab.energy() < Crab::MAX_ENERGY {
+                crab.cook_best(cookbook, &RecipeScoring::default());
+            }
+        }
+        crab.metabolize();
+        crab.grow_older();
+        crab.grow();
+        crab.pantry_mut().age();
+    }
+    for (_, cause) in beach.remove_dead() {
+        match cause {
+            DeathCause::Starvation => report.record_starved(1),
+            DeathCause::OldAge => report.record_died_of_old_age(1),
+        }
+    }
+}
+
+/**
+ * Partitions beaches (by index) into groups such that no two groups hunt in any
+ * of the same reefs. Each group lists its beaches in order.
+ */
+#[cfg(feature = "sync")]
+fn independent_groups(beaches: &[Option<&mut Beach>]) -> Vec<Vec<usize>> {
+    use std::collections::{BTreeMap, HashMap};
+
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    let mut parent: Vec<usize> = (0..beaches.len()).collect();
+    let mut hunted_by: HashMap<usize, usize> = HashMap::new();
+    for (i, beach) in beaches.iter().enumerate() {
+        for crab in beach.as_ref().unwrap().crabs() {
+            for reef in &crab.reefs {
+                let j = *hunted_by.entry(shared::addr(reef)).or_insert(i);
+                let (ri, rj) = (root(&mut parent, i), root(&mut parent, j));
+                parent[ri.max(rj)] = ri.min(rj);
+            }
+        }
+    }
+
+    let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for i in 0..beaches.len() {
+        groups.entry(root(&mut parent, i)).or_default().push(i);
+    }
+    groups.into_values().collect()
+}
+
+impl Default for Ocean {
+    fn default() -> Self {
+        Ocean::new()
     }
 }
@@ -0,0 +1,176 @@
+use crate::diet::Diet;
+use crate::prey::{Prey, PreyKind};
+
+/**
+ * The prey a crab has caught and stored away for later, to eat when it goes
+ * hungry or to cook with (see `Crab::cook`).
+ *
+ * A pantry holds at most `capacity` prey. Stored prey grow older every tick
+ * (see `Prey::freshness`), and are thrown out once they have spoiled or have
+ * been stored for `shelf_life` ticks, whichever comes first.
+ */
+#[derive(Debug)]
+pub struct Pantry {
+    /** Stored prey and the number of ticks each has been stored for, oldest first. */
+    stored: Vec<(Box<dyn Prey>, u32)>,
+    capacity: usize,
+    shelf_life: u32,
+}
+
+impl Pantry {
+    /** The capacity of a new pantry. */
+    pub const DEFAULT_CAPACITY: usize = 5;
+
+    /** The number of ticks prey keep in a new pantry. */
+    pub const DEFAULT_SHELF_LIFE: u32 = 10;
+
+    pub fn new() -> Pantry {
+        Pantry::with_capacity(Pantry::DEFAULT_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Pantry {
+        Pantry {
+            stored: Vec::new(),
+            capacity,
+            shelf_life: Pantry::DEFAULT_SHELF_LIFE,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /**
+     * Sets the capacity of this pantry. Prey already stored beyond the new capacity are kept.
+     */
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+    }
+
+    pub fn shelf_life(&self) -> u32 {
+        self.shelf_life
+    }
+
+    pub fn set_shelf_life(&mut self, shelf_life: u32) {
+        self.shelf_life = shelf_life;
+    }
+
+    /**
+     * Returns the number of prey in the pantry.
+     */
+    pub fn size(&self) -> usize {
+        self.stored.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stored.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.stored.len() >= self.capacity
+    }
+
+    /**
+     * Returns the stored prey, oldest first.
+     */
+    pub fn prey(&self) -> impl Iterator<Item = &dyn Prey> {
+        self.stored.iter().map(|(p, _)| p.as_ref())
+    }
+
+    /**
+     * Returns the stored prey with the number of ticks each has been stored for,
+     * oldest first.
+     */
+    pub fn prey_with_ages(&self) -> impl Iterator<Item = (&dyn Prey, u32)> {
+        self.stored.iter().map(|(p, age)| (p.as_ref(), *age))
+    }
+
+    /**
+     * Stores the given prey at the back of the pantry.
+     *
+     * Returns the prey back if the pantry is already full.
+     */
+    pub fn add_prey(&mut self, prey: Box<dyn Prey>) -> Option<Box<dyn Prey>> {
+        self.add_prey_aged(prey, 0)
+    }
+
+    /**
+     * Like `add_prey`, for prey that have already been stored for `age` ticks.
+     */
+    pub(crate) fn add_prey_aged(&mut self, prey: Box<dyn Prey>, age: u32) -> Option<Box<dyn Prey>> {
+        if self.is_full() {
+            return Some(prey);
+        }
+        self.stored.push((prey, age));
+        None
+    }
+
+    /**
+     * Returns the number of prey of the given kind in the pantry.
+     */
+    pub fn count(&self, kind: &PreyKind) -> usize {
+        self.count_where(|p| &p.kind() == kind)
+    }
+
+    /**
+     * Returns the number of prey in the pantry with the given diet.
+     */
+    pub fn count_diet(&self, diet: Diet) -> usize {
+        self.count_where(|p| p.diet() == diet)
+    }
+
+    /**
+     * Returns the number of prey in the pantry matching `predicate`.
+     */
+    pub fn count_where(&self, predicate: impl Fn(&dyn Prey) -> bool) -> usize {
+        self.prey().filter(|&p| predicate(p)).count()
+    }
+
+    /**
+     * Removes and returns the oldest prey of the given kind, or None if there is none.
+     */
+    pub fn take(&mut self, kind: &PreyKind) -> Option<Box<dyn Prey>> {
+        self.take_where(|p| &p.kind() == kind)
+    }
+
+    /**
+     * Removes and returns the oldest prey with the given diet, or None if there is none.
+     */
+    pub fn take_diet(&mut self, diet: Diet) -> Option<Box<dyn Prey>> {
+        self.take_where(|p| p.diet() == diet)
+    }
+
+    /**
+     * Removes and returns the oldest prey matching `predicate`, or None if there is none.
+     */
+    pub fn take_where(&mut self, predicate: impl Fn(&dyn Prey) -> bool) -> Option<Box<dyn Prey>> {
+        let i = self
+            .stored
+            .iter()
+            .position(|(p, _)| predicate(p.as_ref()))?;
+        Some(self.stored.remove(i).0)
+    }
+
+    /**
+     * Ages every stored prey by one tick (see `Prey::grow_older`), throwing out
+     * the ones that have spoiled or outlived the shelf life. Returns the number
+     * of prey thrown out.
+     */
+    pub fn age(&mut self) -> usize {
+        let before = self.stored.len();
+        for (prey, age) in self.stored.iter_mut() {
+            prey.grow_older();
+            *age += 1;
+        }
+        let shelf_life = self.shelf_life;
+        self.stored
+            .retain(|(prey, age)| *age < shelf_life && !prey.is_spoiled());
+        before - self.stored.len()
+    }
+}
+
+impl Default for Pantry {
+    fn default() -> Self {
+        Pantry::new()
+    }
+}
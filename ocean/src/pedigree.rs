@@ -0,0 +1,230 @@
+use crate::crab::{Crab, CrabId, DeathCause};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct PedigreeEntry {
+    name: String,
+    parents: Option<(CrabId, CrabId)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    death: Option<DeathCause>,
+}
+
+/**
+ * A family tree of every crab that has ever lived on a beach.
+ *
+ * Entries are never removed, so the ancestry of a crab can still be traced
+ * after its ancestors have died.
+ */
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pedigree {
+    entries: BTreeMap<CrabId, PedigreeEntry>,
+}
+
+impl Pedigree {
+    pub fn new() -> Pedigree {
+        Pedigree {
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /**
+     * Records the given crab (and its parents, if it has any) in the pedigree.
+     */
+    pub fn record(&mut self, crab: &Crab) {
+        self.entries.insert(
+            crab.id(),
+            PedigreeEntry {
+                name: String::from(crab.name()),
+                parents: crab.parents(),
+                death: None,
+            },
+        );
+    }
+
+    /**
+     * Copies the entries for every ancestor of the given crab known to `other`
+     * into this pedigree, such as when the crab migrates here from another beach.
+     */
+    pub fn record_ancestry(&mut self, other: &Pedigree, id: CrabId) {
+        for ancestor in other.ancestors(id) {
+            if let Some(entry) = other.entries.get(&ancestor) {
+                self.entries
+                    .entry(ancestor)
+                    .or_insert_with(|| entry.clone());
+            }
+        }
+    }
+
+    /**
+     * Returns the id of the first crab among the given crab and its ancestors
+     * (as `other` knows them) that this pedigree records differently, if any,
+     * such as an unrelated crab that happens to have the same id.
+     */
+    pub(crate) fn conflict(&self, other: &Pedigree, id: CrabId) -> Option<CrabId> {
+        std::iter::once(id).chain(other.ancestors(id)).find(|x| {
+            match (self.entries.get(x), other.entries.get(x)) {
+                (Some(a), Some(b)) => a.name != b.name || a.parents != b.parents,
+                _ => false,
+            }
+        })
+    }
+
+    /**
+     * Returns the number of crabs recorded in the pedigree.
+     */
+    pub fn size(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn contains(&self, id: CrabId) -> bool {
+        self.entries.contains_key(&id)
+    }
+
+    pub fn name(&self, id: CrabId) -> Option<&str> {
+        self.entries.get(&id).map(|e| e.name.as_str())
+    }
+
+    pub fn parents(&self, id: CrabId) -> Option<(CrabId, CrabId)> {
+        self.entries.get(&id).and_then(|e| e.parents)
+    }
+
+    /**
+     * Records that the given crab died, and why. Does nothing if the crab is
+     * not in the pedigree.
+     */
+    pub fn record_death(&mut self, id: CrabId, cause: DeathCause) {
+        if let Some(entry) = self.entries.get_mut(&id) {
+            entry.death = Some(cause);
+        }
+    }
+
+    /**
+     * Returns why the given crab died, or None if it is alive (or unknown).
+     */
+    pub fn cause_of_death(&self, id: CrabId) -> Option<DeathCause> {
+        self.entries.get(&id).and_then(|e| e.death)
+    }
+
+    /**
+     * Returns the ids of every known ancestor of the given crab, not including itself.
+     */
+    pub fn ancestors(&self, id: CrabId) -> BTreeSet<CrabId> {
+        let mut ancestors = BTreeSet::new();
+        let mut todo = vec![id];
+        while let Some(id) = todo.pop() {
+            if let Some((p1, p2)) = self.parents(id) {
+                for parent in [p1, p2] {
+                    if ancestors.insert(parent) {
+                        todo.push(parent);
+                    }
+                }
+            }
+        }
+        ancestors
+    }
+
+    /**
+     * Returns the ids of every known descendant of the given crab, not including itself.
+     */
+    pub fn descendants(&self, id: CrabId) -> BTreeSet<CrabId> {
+        let mut descendants = BTreeSet::new();
+        // Children always have greater ids than their parents, so a single
+        // pass in id order sees every parent before any of its children.
+        for (&child, entry) in self.entries.range(id..) {
+            if let Some((p1, p2)) = entry.parents {
+                let is_descendant = |p| p == id || descendants.contains(&p);
+                if is_descendant(p1) || is_descendant(p2) {
+                    descendants.insert(child);
+                }
+            }
+        }
+        descendants
+    }
+
+    /**
+     * Returns the generation depth of the given crab: 0 for crabs with no known
+     * parents, and otherwise one more than the deeper of its two parents.
+     */
+    pub fn generation(&self, id: CrabId) -> usize {
+        // As in `descendants`, a single pass in id order sees every parent
+        // before any of its children, so each depth is computed only once.
+        let mut depths: HashMap<CrabId, usize> = HashMap::new();
+        for (&crab, entry) in self.entries.range(..=id) {
+            let depth = match entry.parents {
+                None => 0,
+                Some((p1, p2)) => {
+                    let depth_of = |p| depths.get(&p).copied().unwrap_or(0);
+                    1 + depth_of(p1).max(depth_of(p2))
+                }
+            };
+            depths.insert(crab, depth);
+        }
+        depths.get(&id).copied().unwrap_or(0)
+    }
+
+    /**
+     * Returns the coefficient of kinship between two crabs: the probability that
+     * a gene drawn at random from each of them is identical by descent.
+     */
+    pub fn kinship(&self, a: CrabId, b: CrabId) -> f64 {
+        self.kinship_memo(a, b, &mut HashMap::new())
+    }
+
+    /**
+     * Returns Wright's inbreeding coefficient of the given crab, which is the
+     * kinship of its parents (or 0 if its parents are unknown).
+     */
+    pub fn inbreeding(&self, id: CrabId) -> f64 {
+        self.inbreeding_memo(id, &mut HashMap::new())
+    }
+
+    fn inbreeding_memo(&self, id: CrabId, memo: &mut HashMap<(CrabId, CrabId), f64>) -> f64 {
+        match self.parents(id) {
+            None => 0.0,
+            Some((p1, p2)) => self.kinship_memo(p1, p2, memo),
+        }
+    }
+
+    fn kinship_memo(&self, a: CrabId, b: CrabId, memo: &mut HashMap<(CrabId, CrabId), f64>) -> f64 {
+        if a == b {
+            return 0.5 * (1.0 + self.inbreeding_memo(a, memo));
+        }
+
+        // Always recurse through the younger crab, which cannot be an ancestor of the older.
+        let (older, younger) = if a < b { (a, b) } else { (b, a) };
+        if let Some(&k) = memo.get(&(older, younger)) {
+            return k;
+        }
+
+        let k = match self.parents(younger) {
+            None => 0.0,
+            Some((p1, p2)) => {
+                0.5 * (self.kinship_memo(older, p1, memo) + self.kinship_memo(older, p2, memo))
+            }
+        };
+        memo.insert((older, younger), k);
+        k
+    }
+
+    /**
+     * Exports the pedigree as a Graphviz DOT digraph, with an edge from each parent to each child.
+     */
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph pedigree {\n");
+        for (id, entry) in &self.entries {
+            let label = entry.name.replace('\\', "\\\\").replace('"', "\\\"");
+            writeln!(dot, "    crab{} [label=\"{}\"];", id, label).unwrap();
+        }
+        for (id, entry) in &self.entries {
+            if let Some((p1, p2)) = entry.parents {
+                writeln!(dot, "    crab{} -> crab{};", p1, id).unwrap();
+                writeln!(dot, "    crab{} -> crab{};", p2, id).unwrap();
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
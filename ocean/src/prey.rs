@@ -1,10 +1,47 @@
 use crate::crab::Crab;
 use crate::diet::Diet;
+use crate::rand::OceanRng;
+use crate::shared::MaybeSend;
+use std::collections::BTreeMap;
+use std::fmt;
 
-pub trait Prey {
+/**
+ * With the `sync` feature, all `Prey` must be `Send + Sync`, so that reefs can be shared between threads.
+ */
+pub trait Prey: MaybeSend {
     /** What diet does this `Prey` fit into? */
     fn diet(&self) -> Diet;
 
+    /**
+     * What kind of `Prey` is this?
+     *
+     * By default, this is derived from the tag of `to_record`, so custom prey
+     * are `PreyKind::Custom` with their tag as the name.
+     */
+    fn kind(&self) -> PreyKind {
+        PreyKind::from_tag(self.to_record().tag())
+    }
+
+    /**
+     * A short human-readable description of this `Prey` and its state, such as
+     * `Shrimp (energy 1)`. Used by `Debug` and `Display`.
+     *
+     * By default, this lists the parameters of `to_record`.
+     */
+    fn describe(&self) -> String {
+        let record = self.to_record();
+        let params: Vec<String> = record
+            .params
+            .iter()
+            .map(|(param, value)| format!("{} {}", param, value))
+            .collect();
+        if params.is_empty() {
+            self.kind().to_string()
+        } else {
+            format!("{} ({})", self.kind(), params.join(", "))
+        }
+    }
+
     /**
      * `Prey` are eaten by `Crab`s. This method is called when a crab tries to
      * eat this prey. Return true if the prey gets away, and false if it does not.
@@ -12,22 +49,216 @@ pub trait Prey {
      * See the implementations below for some examples of different behaviors.
      */
     fn try_escape(&mut self, crab: &Crab) -> bool;
+
+    /** How much energy does a `Crab` gain from eating this `Prey`? */
+    fn nutrition(&self) -> u32;
+
+    /**
+     * Describes this `Prey` as plain data: a tag naming its concrete type, and
+     * its current state. A `PreyRegistry` can turn the record back into `Prey`.
+     */
+    fn to_record(&self) -> PreyRecord;
+
+    /**
+     * Called once per tick by `Reef::regenerate`. Returns Some offspring of this
+     * `Prey` if it reproduces this tick.
+     *
+     * `kin` is the number of prey of the same type in the reef, including this one.
+     * By default, prey do not reproduce.
+     */
+    fn reproduce(&self, _kin: usize, _rng: &mut OceanRng) -> Option<Box<dyn Prey>> {
+        None
+    }
+
+    /**
+     * The number of ticks since this `Prey` was caught. Prey alive in a reef
+     * have age zero.
+     *
+     * By default, prey do not keep track of their age, and never spoil.
+     */
+    fn age(&self) -> u32 {
+        0
+    }
+
+    /**
+     * Sets the age of this `Prey`. `PreyRegistry::build` calls this with the
+     * record's `age` parameter, so prey that track their age should include it
+     * in `to_record`.
+     */
+    fn set_age(&mut self, _age: u32) {}
+
+    /**
+     * Called once per tick for caught prey (such as in a crab's `Pantry`),
+     * making them a tick older.
+     */
+    fn grow_older(&mut self) {
+        self.set_age(self.age().saturating_add(1));
+    }
+
+    /** How much freshness this `Prey` loses for every tick of its age. */
+    fn spoil_rate(&self) -> u32 {
+        0
+    }
+
+    /**
+     * How fresh this `Prey` is, from `MAX_FRESHNESS` when caught down to zero
+     * once it has spoiled.
+     */
+    fn freshness(&self) -> u32 {
+        MAX_FRESHNESS.saturating_sub(self.age().saturating_mul(self.spoil_rate()))
+    }
+
+    fn is_spoiled(&self) -> bool {
+        self.freshness() == 0
+    }
 }
 
-impl core::fmt::Debug for dyn Prey {
-    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-        write!(f, "Prey")
+/** The freshness of prey that have just been caught. */
+pub const MAX_FRESHNESS: u32 = 100;
+
+/**
+ * Adds the `age` parameter to the record of a built-in prey, unless it is zero.
+ */
+fn with_age(record: PreyRecord, age: u32) -> PreyRecord {
+    if age > 0 {
+        record.with("age", age)
+    } else {
+        record
+    }
+}
+
+/**
+ * A plain-data description of some `Prey`, used to save and restore trait objects.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PreyRecord {
+    tag: String,
+    params: BTreeMap<String, u32>,
+}
+
+impl PreyRecord {
+    pub fn new(tag: &str) -> PreyRecord {
+        PreyRecord {
+            tag: String::from(tag),
+            params: BTreeMap::new(),
+        }
+    }
+
+    /**
+     * Returns this record with the named parameter set to `value`.
+     */
+    pub fn with(mut self, param: &str, value: u32) -> PreyRecord {
+        self.params.insert(String::from(param), value);
+        self
+    }
+
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    pub fn get(&self, param: &str) -> Option<u32> {
+        self.params.get(param).copied()
+    }
+}
+
+/**
+ * The kinds of `Prey` in the ocean. Prey types defined outside this module are `Custom`.
+ */
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PreyKind {
+    Minnow,
+    Shrimp,
+    Clam,
+    Algae,
+    Custom(String),
+}
+
+impl PreyKind {
+    /**
+     * Returns the kind named by a `PreyRecord` tag.
+     */
+    pub fn from_tag(tag: &str) -> PreyKind {
+        use PreyKind::*;
+        match tag {
+            "minnow" => Minnow,
+            "shrimp" => Shrimp,
+            "clam" => Clam,
+            "algae" => Algae,
+            _ => Custom(String::from(tag)),
+        }
+    }
+
+    /**
+     * Returns the `PreyRecord` tag for this kind.
+     */
+    pub fn tag(&self) -> &str {
+        use PreyKind::*;
+        match self {
+            Minnow => "minnow",
+            Shrimp => "shrimp",
+            Clam => "clam",
+            Algae => "algae",
+            Custom(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for PreyKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use PreyKind::*;
+        match self {
+            Minnow => write!(f, "Minnow"),
+            Shrimp => write!(f, "Shrimp"),
+            Clam => write!(f, "Clam"),
+            Algae => write!(f, "Algae"),
+            Custom(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl fmt::Debug for dyn Prey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.describe())
+    }
+}
+
+impl fmt::Display for dyn Prey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.describe())
+    }
+}
+
+/**
+ * The parameters `Ocean::generate_reef` creates new prey with.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PreyParams {
+    pub minnow_speed: u32,
+    pub shrimp_energy: u32,
+}
+
+impl Default for PreyParams {
+    fn default() -> Self {
+        PreyParams {
+            minnow_speed: 25,
+            shrimp_energy: 1,
+        }
     }
 }
 
 #[derive(Debug)]
 pub struct Shrimp {
     energy: u32,
+    age: u32,
 }
 
 impl Shrimp {
     pub fn new(energy: u32) -> Shrimp {
-        Shrimp { energy }
+        Shrimp { energy, age: 0 }
     }
 }
 
@@ -36,29 +267,68 @@ impl Prey for Shrimp {
         Diet::Shellfish
     }
 
+    fn kind(&self) -> PreyKind {
+        PreyKind::Shrimp
+    }
+
     /**
      * Shrimp move in bursts, and can escape from crabs if they have enough energy.
      */
     fn try_escape(&mut self, _crab: &Crab) -> bool {
         if self.energy == 0 {
             // No remaining energy to escape!
-            return false;
+            false
         } else {
             // Escaped, but getting more tired...
             self.energy -= 1;
-            return true;
+            true
         }
     }
+
+    fn nutrition(&self) -> u32 {
+        3
+    }
+
+    fn to_record(&self) -> PreyRecord {
+        with_age(
+            PreyRecord::new(PreyKind::Shrimp.tag()).with("energy", self.energy),
+            self.age,
+        )
+    }
+
+    /**
+     * Shrimp breed in pairs. Baby shrimp have enough energy for a single escape.
+     */
+    fn reproduce(&self, kin: usize, rng: &mut OceanRng) -> Option<Box<dyn Prey>> {
+        if kin >= 2 && rng.chance(0.1) {
+            Some(Box::new(Shrimp::new(1)))
+        } else {
+            None
+        }
+    }
+
+    fn age(&self) -> u32 {
+        self.age
+    }
+
+    fn set_age(&mut self, age: u32) {
+        self.age = age;
+    }
+
+    fn spoil_rate(&self) -> u32 {
+        8
+    }
 }
 
 #[derive(Debug)]
 pub struct Minnow {
     speed: u32,
+    age: u32,
 }
 
 impl Minnow {
     pub fn new(speed: u32) -> Minnow {
-        Minnow { speed }
+        Minnow { speed, age: 0 }
     }
 }
 
@@ -67,20 +337,69 @@ impl Prey for Minnow {
         Diet::Fish
     }
 
+    fn kind(&self) -> PreyKind {
+        PreyKind::Minnow
+    }
+
     /**
      * Minnows are fast and tireless, and can always escape from crabs faster than them.
      */
     fn try_escape(&mut self, crab: &Crab) -> bool {
         self.speed > crab.speed()
     }
+
+    fn nutrition(&self) -> u32 {
+        5
+    }
+
+    fn to_record(&self) -> PreyRecord {
+        with_age(
+            PreyRecord::new(PreyKind::Minnow.tag()).with("speed", self.speed),
+            self.age,
+        )
+    }
+
+    /**
+     * Minnows breed in pairs, and are born as fast as their parent.
+     */
+    fn reproduce(&self, kin: usize, rng: &mut OceanRng) -> Option<Box<dyn Prey>> {
+        if kin >= 2 && rng.chance(0.1) {
+            Some(Box::new(Minnow::new(self.speed)))
+        } else {
+            None
+        }
+    }
+
+    fn age(&self) -> u32 {
+        self.age
+    }
+
+    fn set_age(&mut self, age: u32) {
+        self.age = age;
+    }
+
+    /**
+     * Fish go off quickly.
+     */
+    fn spoil_rate(&self) -> u32 {
+        10
+    }
 }
 
 #[derive(Debug)]
-pub struct Algae {}
+pub struct Algae {
+    age: u32,
+}
 
 impl Algae {
     pub fn new() -> Algae {
-        Algae {}
+        Algae { age: 0 }
+    }
+}
+
+impl Default for Algae {
+    fn default() -> Self {
+        Algae::new()
     }
 }
 
@@ -89,20 +408,63 @@ impl Prey for Algae {
         Diet::Plants
     }
 
+    fn kind(&self) -> PreyKind {
+        PreyKind::Algae
+    }
+
     /**
      * Algae can't move. They're plants.
      */
     fn try_escape(&mut self, _crab: &Crab) -> bool {
-        return false;
+        false
+    }
+
+    fn nutrition(&self) -> u32 {
+        2
+    }
+
+    fn to_record(&self) -> PreyRecord {
+        with_age(PreyRecord::new(PreyKind::Algae.tag()), self.age)
+    }
+
+    /**
+     * Algae regrow at a fixed rate, no matter how much of it is left.
+     */
+    fn reproduce(&self, _kin: usize, rng: &mut OceanRng) -> Option<Box<dyn Prey>> {
+        if rng.chance(0.2) {
+            Some(Box::new(Algae::new()))
+        } else {
+            None
+        }
+    }
+
+    fn age(&self) -> u32 {
+        self.age
+    }
+
+    fn set_age(&mut self, age: u32) {
+        self.age = age;
+    }
+
+    fn spoil_rate(&self) -> u32 {
+        5
     }
 }
 
 #[derive(Debug)]
-pub struct Clam {}
+pub struct Clam {
+    age: u32,
+}
 
 impl Clam {
     pub fn new() -> Clam {
-        Clam {}
+        Clam { age: 0 }
+    }
+}
+
+impl Default for Clam {
+    fn default() -> Self {
+        Clam::new()
     }
 }
 
@@ -111,10 +473,48 @@ impl Prey for Clam {
         Diet::Shellfish
     }
 
+    fn kind(&self) -> PreyKind {
+        PreyKind::Clam
+    }
+
     /**
      * There is no escape for the clam.
      */
     fn try_escape(&mut self, _crab: &Crab) -> bool {
-        return false;
+        false
+    }
+
+    fn nutrition(&self) -> u32 {
+        4
+    }
+
+    fn to_record(&self) -> PreyRecord {
+        with_age(PreyRecord::new(PreyKind::Clam.tag()), self.age)
+    }
+
+    /**
+     * Clams spawn slowly.
+     */
+    fn reproduce(&self, _kin: usize, rng: &mut OceanRng) -> Option<Box<dyn Prey>> {
+        if rng.chance(0.02) {
+            Some(Box::new(Clam::new()))
+        } else {
+            None
+        }
+    }
+
+    fn age(&self) -> u32 {
+        self.age
+    }
+
+    fn set_age(&mut self, age: u32) {
+        self.age = age;
+    }
+
+    /**
+     * Clams keep well in their shells.
+     */
+    fn spoil_rate(&self) -> u32 {
+        4
     }
 }
@@ -1,4 +1,10 @@
-use crate::prey::Prey;
+use crate::distribution::Distribution;
+use crate::error::OceanError;
+use crate::grid::Position;
+use crate::prey::{Prey, PreyKind, PreyRecord};
+use crate::rand::OceanRng;
+use crate::registry::PreyRegistry;
+use std::collections::{BTreeMap, HashMap};
 
 // VecDeque is Rust's implementation of a double-ended queue, and
 // is used only if we only need to use it in a single-ended manner.
@@ -7,19 +13,55 @@ use std::collections::vec_deque::{Iter, VecDeque};
 #[derive(Debug)]
 pub struct Reef {
     prey: VecDeque<Box<dyn Prey>>,
+    capacity: usize,
+    position: Position,
 }
 
 impl Reef {
+    /** The carrying capacity of a reef created with `new`. */
+    pub const DEFAULT_CAPACITY: usize = 100;
+
     pub fn new() -> Self {
-        unimplemented!();
+        Reef::with_capacity(Reef::DEFAULT_CAPACITY)
+    }
+
+    /**
+     * Creates an empty reef whose prey stop reproducing once it holds `capacity` prey.
+     */
+    pub fn with_capacity(capacity: usize) -> Self {
+        Reef {
+            prey: VecDeque::new(),
+            capacity,
+            position: Position::default(),
+        }
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    pub fn set_position(&mut self, position: Position) {
+        self.position = position;
+    }
+
+    /**
+     * Returns the carrying capacity of this reef. Prey may still be added past it
+     * with `add_prey`, but `regenerate` will never grow the reef beyond it.
+     */
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
     }
 
-    pub fn prey(&self) -> Iter<Box<dyn Prey>> {
-        unimplemented!();
+    pub fn prey(&self) -> Iter<'_, Box<dyn Prey>> {
+        self.prey.iter()
     }
 
     pub fn population(&self) -> usize {
-        unimplemented!();
+        self.prey.len()
     }
 
     /**
@@ -28,7 +70,7 @@ impl Reef {
      * This function takes ownership of the boxed prey.
      */
     pub fn add_prey(&mut self, prey: Box<dyn Prey>) {
-        unimplemented!();
+        self.prey.push_back(prey);
     }
 
     /**
@@ -37,6 +79,172 @@ impl Reef {
      * The callee of this function receives ownership of the boxed prey.
      */
     pub fn take_prey(&mut self) -> Option<Box<dyn Prey>> {
-        unimplemented!();
+        self.prey.pop_front()
+    }
+
+    /**
+     * Gives every prey in the reef a chance to reproduce (see `Prey::reproduce`),
+     * adding the offspring to the back of the reef until it reaches its capacity.
+     *
+     * Returns the number of prey born.
+     */
+    pub fn regenerate(&mut self, rng: &mut OceanRng) -> usize {
+        let mut kin: HashMap<PreyKind, usize> = HashMap::new();
+        for prey in &self.prey {
+            *kin.entry(prey.kind()).or_insert(0) += 1;
+        }
+
+        let offspring: Vec<Box<dyn Prey>> = self
+            .prey
+            .iter()
+            .filter_map(|p| p.reproduce(kin[&p.kind()], rng))
+            .collect();
+
+        let room = self.capacity.saturating_sub(self.prey.len());
+        let born = offspring.len().min(room);
+        self.prey.extend(offspring.into_iter().take(born));
+        born
+    }
+}
+
+impl Default for Reef {
+    fn default() -> Self {
+        Reef::new()
+    }
+}
+
+/**
+ * A recipe for generating a reef: how many of each species of prey it holds,
+ * and the distribution each of their parameters is sampled from.
+ *
+ * Species can be any prey registered in a `PreyRegistry`, including custom ones:
+ *
+ * ```
+ * use ocean::distribution::Distribution;
+ * use ocean::ocean::Ocean;
+ * use ocean::reef::ReefSpec;
+ *
+ * let spec = ReefSpec::new()
+ *     .minnows(40, Distribution::Normal { mean: 25.0, std_dev: 5.0 })
+ *     .shrimp(10, Distribution::Uniform { min: 0, max: 3 })
+ *     .algae(20);
+ *
+ * let mut ocean = Ocean::new();
+ * let reef = ocean.generate_reef_from(&spec).unwrap();
+ * ```
+ */
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReefSpec {
+    capacity: usize,
+    position: Position,
+    species: Vec<SpeciesSpec>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct SpeciesSpec {
+    tag: String,
+    count: u32,
+    params: BTreeMap<String, Distribution>,
+}
+
+impl ReefSpec {
+    pub fn new() -> ReefSpec {
+        ReefSpec {
+            capacity: Reef::DEFAULT_CAPACITY,
+            position: Position::default(),
+            species: Vec::new(),
+        }
+    }
+
+    /**
+     * Sets the carrying capacity of the generated reef.
+     */
+    pub fn capacity(mut self, capacity: usize) -> ReefSpec {
+        self.capacity = capacity;
+        self
+    }
+
+    /**
+     * Sets where the generated reef is placed on the ocean's grid.
+     */
+    pub fn position(mut self, position: Position) -> ReefSpec {
+        self.position = position;
+        self
+    }
+
+    pub fn minnows(self, count: u32, speed: Distribution) -> ReefSpec {
+        self.species(PreyKind::Minnow.tag(), count, [("speed", speed)])
+    }
+
+    pub fn shrimp(self, count: u32, energy: Distribution) -> ReefSpec {
+        self.species(PreyKind::Shrimp.tag(), count, [("energy", energy)])
+    }
+
+    pub fn clams(self, count: u32) -> ReefSpec {
+        self.species(PreyKind::Clam.tag(), count, [])
+    }
+
+    pub fn algae(self, count: u32) -> ReefSpec {
+        self.species(PreyKind::Algae.tag(), count, [])
+    }
+
+    /**
+     * Adds `count` prey with the given registry tag, each with its parameters
+     * sampled from the given distributions.
+     */
+    pub fn species<'a>(
+        mut self,
+        tag: &str,
+        count: u32,
+        params: impl IntoIterator<Item = (&'a str, Distribution)>,
+    ) -> ReefSpec {
+        self.species.push(SpeciesSpec {
+            tag: String::from(tag),
+            count,
+            params: params
+                .into_iter()
+                .map(|(param, d)| (String::from(param), d))
+                .collect(),
+        });
+        self
+    }
+
+    /**
+     * Generates a reef from this spec, in the order species were added.
+     *
+     * Returns an error if a species is not registered in `registry`, or its
+     * constructor rejects the sampled parameters.
+     */
+    pub fn build(&self, registry: &PreyRegistry, rng: &mut OceanRng) -> Result<Reef, OceanError> {
+        let mut reef = Reef::with_capacity(self.capacity);
+        reef.set_position(self.position);
+        for species in &self.species {
+            if !registry.contains(&species.tag) {
+                return Err(OceanError::UnknownPrey {
+                    tag: species.tag.clone(),
+                });
+            }
+            for _ in 0..species.count {
+                let record = species
+                    .params
+                    .iter()
+                    .fold(PreyRecord::new(&species.tag), |record, (param, d)| {
+                        record.with(param, d.sample(rng))
+                    });
+                let prey = registry.build(&record).ok_or(OceanError::InvalidPrey {
+                    tag: species.tag.clone(),
+                })?;
+                reef.add_prey(prey);
+            }
+        }
+        Ok(reef)
+    }
+}
+
+impl Default for ReefSpec {
+    fn default() -> Self {
+        ReefSpec::new()
     }
 }
@@ -0,0 +1,136 @@
+use crate::prey::{Algae, Clam, Minnow, Prey, PreyKind, PreyRecord, Shrimp};
+use crate::strategy::{
+    GiveUpAfter, HuntStrategy, InOrder, RememberSuccess, RichestFirst, RoundRobin, StrategyRecord,
+};
+use std::collections::BTreeMap;
+
+/**
+ * Builds `Prey` from a record, or returns None if the record is malformed.
+ */
+pub type PreyConstructor = fn(&PreyRecord) -> Option<Box<dyn Prey>>;
+
+/**
+ * Maps the tags of `PreyRecord`s to constructors for the concrete `Prey` they describe.
+ *
+ * A new registry knows about all of the built-in prey. Custom prey types can be
+ * added with `register`.
+ */
+#[derive(Debug, Clone)]
+pub struct PreyRegistry {
+    constructors: BTreeMap<String, PreyConstructor>,
+}
+
+impl PreyRegistry {
+    pub fn new() -> PreyRegistry {
+        let mut registry = PreyRegistry {
+            constructors: BTreeMap::new(),
+        };
+        registry.register(PreyKind::Minnow.tag(), |r| {
+            Some(Box::new(Minnow::new(r.get("speed")?)))
+        });
+        registry.register(PreyKind::Shrimp.tag(), |r| {
+            Some(Box::new(Shrimp::new(r.get("energy")?)))
+        });
+        registry.register(PreyKind::Clam.tag(), |_| Some(Box::new(Clam::new())));
+        registry.register(PreyKind::Algae.tag(), |_| Some(Box::new(Algae::new())));
+        registry
+    }
+
+    /**
+     * Registers a constructor for records with the given tag, replacing any existing one.
+     */
+    pub fn register(&mut self, tag: &str, constructor: PreyConstructor) {
+        self.constructors.insert(String::from(tag), constructor);
+    }
+
+    pub fn contains(&self, tag: &str) -> bool {
+        self.constructors.contains_key(tag)
+    }
+
+    /**
+     * Builds the `Prey` described by `record`, with the age given by its `age`
+     * parameter (zero if missing).
+     *
+     * Returns None if the record's tag is not registered, or the record is malformed.
+     */
+    pub fn build(&self, record: &PreyRecord) -> Option<Box<dyn Prey>> {
+        let mut prey = self.constructors.get(record.tag())?(record)?;
+        if let Some(age) = record.get("age") {
+            prey.set_age(age);
+        }
+        Some(prey)
+    }
+}
+
+impl Default for PreyRegistry {
+    fn default() -> Self {
+        PreyRegistry::new()
+    }
+}
+
+/**
+ * Builds a `HuntStrategy` from a record, or returns None if the record is malformed.
+ */
+pub type StrategyConstructor = fn(&StrategyRecord) -> Option<Box<dyn HuntStrategy>>;
+
+/**
+ * Maps the tags of `StrategyRecord`s to constructors for the concrete
+ * `HuntStrategy` they describe.
+ *
+ * A new registry knows about all of the built-in strategies. Custom strategies
+ * can be added with `register`.
+ */
+#[derive(Debug, Clone)]
+pub struct StrategyRegistry {
+    constructors: BTreeMap<String, StrategyConstructor>,
+}
+
+impl StrategyRegistry {
+    pub fn new() -> StrategyRegistry {
+        let mut registry = StrategyRegistry {
+            constructors: BTreeMap::new(),
+        };
+        registry.register("in_order", |_| Some(Box::new(InOrder)));
+        registry.register("round_robin", |r| {
+            Some(Box::new(RoundRobin {
+                next: r.get("next")? as usize,
+            }))
+        });
+        registry.register("richest_first", |_| Some(Box::new(RichestFirst)));
+        registry.register("remember_success", |r| {
+            Some(Box::new(RememberSuccess {
+                last: r.get("last").map(|i| i as usize),
+            }))
+        });
+        registry.register("give_up_after", |r| {
+            Some(Box::new(GiveUpAfter::new(r.get("attempts")?)))
+        });
+        registry
+    }
+
+    /**
+     * Registers a constructor for records with the given tag, replacing any existing one.
+     */
+    pub fn register(&mut self, tag: &str, constructor: StrategyConstructor) {
+        self.constructors.insert(String::from(tag), constructor);
+    }
+
+    pub fn contains(&self, tag: &str) -> bool {
+        self.constructors.contains_key(tag)
+    }
+
+    /**
+     * Builds the `HuntStrategy` described by `record`.
+     *
+     * Returns None if the record's tag is not registered, or the record is malformed.
+     */
+    pub fn build(&self, record: &StrategyRecord) -> Option<Box<dyn HuntStrategy>> {
+        self.constructors.get(record.tag())?(record)
+    }
+}
+
+impl Default for StrategyRegistry {
+    fn default() -> Self {
+        StrategyRegistry::new()
+    }
+}
@@ -0,0 +1,272 @@
+use crate::prey::PreyKind;
+
+/**
+ * A summary of what happened in the ocean during a single tick of the simulation.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TickReport {
+    tick: u64,
+    crabs: usize,
+    fed: usize,
+    hungry: usize,
+    starved: usize,
+    died_of_old_age: usize,
+    prey_born: usize,
+    prey_remaining: usize,
+    reefs_discovered: usize,
+    reefs_forgotten: usize,
+    migrated: usize,
+    evicted: usize,
+}
+
+impl TickReport {
+    /** The header row matching `to_csv_row`. */
+    pub const CSV_HEADER: &'static str = concat!(
+        "tick,crabs,fed,hungry,starved,prey_born,prey_remaining,",
+        "reefs_discovered,reefs_forgotten,migrated,evicted,died_of_old_age"
+    );
+
+    pub fn new(tick: u64) -> TickReport {
+        TickReport {
+            tick,
+            crabs: 0,
+            fed: 0,
+            hungry: 0,
+            starved: 0,
+            died_of_old_age: 0,
+            prey_born: 0,
+            prey_remaining: 0,
+            reefs_discovered: 0,
+            reefs_forgotten: 0,
+            migrated: 0,
+            evicted: 0,
+        }
+    }
+
+    /** The tick this report describes, starting from 1. */
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /** The number of crabs that went hunting this tick. */
+    pub fn crabs(&self) -> usize {
+        self.crabs
+    }
+
+    /** The number of crabs that caught something this tick. */
+    pub fn fed(&self) -> usize {
+        self.fed
+    }
+
+    /** The number of crabs that came back empty-handed this tick. */
+    pub fn hungry(&self) -> usize {
+        self.hungry
+    }
+
+    /** The number of crabs that starved to death and were removed from their beach this tick. */
+    pub fn starved(&self) -> usize {
+        self.starved
+    }
+
+    /** The number of crabs that died of old age and were removed from their beach this tick. */
+    pub fn died_of_old_age(&self) -> usize {
+        self.died_of_old_age
+    }
+
+    /** The number of prey born in all reefs this tick. */
+    pub fn prey_born(&self) -> usize {
+        self.prey_born
+    }
+
+    /** The total number of prey left across all reefs at the end of the tick. */
+    pub fn prey_remaining(&self) -> usize {
+        self.prey_remaining
+    }
+
+    /** The number of reefs crabs discovered (or learned of from each other) this tick. */
+    pub fn reefs_discovered(&self) -> usize {
+        self.reefs_discovered
+    }
+
+    /** The number of reefs crabs forgot this tick. */
+    pub fn reefs_forgotten(&self) -> usize {
+        self.reefs_forgotten
+    }
+
+    /** The number of crabs that moved to another beach this tick. */
+    pub fn migrated(&self) -> usize {
+        self.migrated
+    }
+
+    /** The number of crabs evicted from overflowing beaches this tick. */
+    pub fn evicted(&self) -> usize {
+        self.evicted
+    }
+
+    /**
+     * Records the outcome of a single crab's hunt.
+     */
+    pub fn record_hunt(&mut self, caught: bool) {
+        self.crabs += 1;
+        if caught {
+            self.fed += 1;
+        } else {
+            self.hungry += 1;
+        }
+    }
+
+    pub fn record_starved(&mut self, starved: usize) {
+        self.starved += starved;
+    }
+
+    pub fn record_died_of_old_age(&mut self, died: usize) {
+        self.died_of_old_age += died;
+    }
+
+    pub fn record_prey_born(&mut self, born: usize) {
+        self.prey_born += born;
+    }
+
+    pub fn record_reef_discovered(&mut self) {
+        self.reefs_discovered += 1;
+    }
+
+    pub fn record_reefs_forgotten(&mut self, forgotten: usize) {
+        self.reefs_forgotten += forgotten;
+    }
+
+    pub fn record_migrated(&mut self) {
+        self.migrated += 1;
+    }
+
+    pub fn record_evicted(&mut self, evicted: usize) {
+        self.evicted += evicted;
+    }
+
+    /**
+     * Adds the counts recorded in `other` into this report.
+     */
+    #[cfg(feature = "sync")]
+    pub(crate) fn merge(&mut self, other: &TickReport) {
+        self.crabs += other.crabs;
+        self.fed += other.fed;
+        self.hungry += other.hungry;
+        self.starved += other.starved;
+        self.died_of_old_age += other.died_of_old_age;
+        self.prey_born += other.prey_born;
+        self.reefs_discovered += other.reefs_discovered;
+        self.reefs_forgotten += other.reefs_forgotten;
+        self.migrated += other.migrated;
+        self.evicted += other.evicted;
+    }
+
+    pub fn set_prey_remaining(&mut self, prey_remaining: usize) {
+        self.prey_remaining = prey_remaining;
+    }
+
+    /**
+     * Formats this report as a row of comma-separated values, in the order of `CSV_HEADER`.
+     */
+    pub fn to_csv_row(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{}",
+            self.tick,
+            self.crabs,
+            self.fed,
+            self.hungry,
+            self.starved,
+            self.prey_born,
+            self.prey_remaining,
+            self.reefs_discovered,
+            self.reefs_forgotten,
+            self.migrated,
+            self.evicted,
+            self.died_of_old_age
+        )
+    }
+}
+
+/**
+ * The outcome of a single crab's hunt (see `Crab::hunt_detailed`).
+ */
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct HuntReport {
+    caught: Option<(PreyKind, usize)>,
+    stored: bool,
+    escaped: Vec<PreyKind>,
+    inedible: Vec<PreyKind>,
+    visited: Vec<usize>,
+    travel_time: u32,
+}
+
+impl HuntReport {
+    pub fn new() -> HuntReport {
+        HuntReport::default()
+    }
+
+    /** Returns true if the crab caught something. */
+    pub fn is_success(&self) -> bool {
+        self.caught.is_some()
+    }
+
+    /** The kind of prey the crab caught and ate, if any. */
+    pub fn caught(&self) -> Option<&PreyKind> {
+        self.caught.as_ref().map(|(kind, _)| kind)
+    }
+
+    /** The index (in the crab's reefs) of the reef the prey was caught in, if any. */
+    pub fn reef(&self) -> Option<usize> {
+        self.caught.as_ref().map(|&(_, reef)| reef)
+    }
+
+    /** Returns true if the crab stored its catch in its pantry instead of eating it. */
+    pub fn is_stored(&self) -> bool {
+        self.stored
+    }
+
+    /** The number of prey the crab tried to catch, including any it caught. */
+    pub fn attempts(&self) -> usize {
+        self.escaped.len() + self.inedible.len() + usize::from(self.is_success())
+    }
+
+    /** The kinds of prey that escaped the crab, in the order it tried them. */
+    pub fn escaped(&self) -> &[PreyKind] {
+        &self.escaped
+    }
+
+    /** The kinds of prey the crab caught but could not eat, in the order it tried them. */
+    pub fn inedible(&self) -> &[PreyKind] {
+        &self.inedible
+    }
+
+    /** The indices (in the crab's reefs) of the reefs the crab travelled to, in order. */
+    pub fn visited(&self) -> &[usize] {
+        &self.visited
+    }
+
+    /** The total number of ticks the crab spent travelling to reefs. */
+    pub fn travel_time(&self) -> u32 {
+        self.travel_time
+    }
+
+    pub fn record_caught(&mut self, kind: PreyKind, reef: usize) {
+        self.caught = Some((kind, reef));
+    }
+
+    pub fn record_stored(&mut self) {
+        self.stored = true;
+    }
+
+    pub fn record_escaped(&mut self, kind: PreyKind) {
+        self.escaped.push(kind);
+    }
+
+    pub fn record_inedible(&mut self, kind: PreyKind) {
+        self.inedible.push(kind);
+    }
+
+    pub fn record_travel(&mut self, reef: usize, ticks: u32) {
+        self.visited.push(reef);
+        self.travel_time = self.travel_time.saturating_add(ticks);
+    }
+}
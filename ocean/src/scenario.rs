@@ -0,0 +1,227 @@
+use crate::beach::Overflow;
+use crate::color::Color;
+use crate::crab::Crab;
+use crate::diet::{Diet, DietSet};
+use crate::distribution::Distribution;
+use crate::exploration::ExplorationRates;
+use crate::grid::Position;
+use crate::migration::MigrationPolicy;
+use crate::ocean::Ocean;
+use crate::prey::PreyParams;
+use crate::shared::{self, SharedReef};
+use std::fmt;
+
+/**
+ * A description of an ocean to simulate: its seed, reefs and beaches.
+ *
+ * With the `serde` feature, scenarios can be loaded from JSON files like:
+ *
+ * ```json
+ * {
+ *   "seed": 42,
+ *   "prey_params": { "minnow_speed": 25, "shrimp_energy": 1 },
+ *   "exploration": { "discovery": 0.1, "sharing": 0.25, "forget_after": 5 },
+ *   "migration": { "crowded_at": 0.9, "crowding": 0.2, "scarcity": 0.1 },
+ *   "lifespan": { "Normal": { "mean": 100.0, "std_dev": 10.0 } },
+ *   "reefs": [{ "minnows": 5, "shrimp": 5, "clams": 5, "algae": 5, "position": { "x": 3, "y": 4 } }],
+ *   "beaches": [
+ *     {
+ *       "position": { "x": 0, "y": 0 },
+ *       "crabs": [
+ *         {
+ *           "name": "Prinz",
+ *           "speed": 20,
+ *           "color": { "r": 255, "g": 215, "b": 0 },
+ *           "diet": "Shellfish",
+ *           "reefs": [0]
+ *         }
+ *       ]
+ *     }
+ *   ]
+ * }
+ * ```
+ *
+ * Reefs and beaches are placed at the origin unless given a `position`. Crabs
+ * only know the reefs they list, unless the scenario gives `exploration` rates
+ * (see `ExplorationRates`), and stay on their beach unless it gives a
+ * `migration` policy (see `MigrationPolicy`). Crabs live forever, unless the
+ * scenario gives a `lifespan` distribution (see `Ocean::set_lifespan`).
+ */
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scenario {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub seed: u64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub prey_params: PreyParams,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub exploration: Option<ExplorationRates>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub migration: Option<MigrationPolicy>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub lifespan: Option<Distribution>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reefs: Vec<ReefScenario>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub beaches: Vec<BeachScenario>,
+}
+
+/**
+ * The number of each kind of prey in a reef, as in `Ocean::generate_reef`,
+ * and where the reef is.
+ */
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ReefScenario {
+    pub minnows: u32,
+    pub shrimp: u32,
+    pub clams: u32,
+    pub algae: u32,
+    pub position: Position,
+}
+
+/**
+ * A beach and its crabs. Beaches hold any number of crabs unless given a
+ * `capacity`; see `Overflow` and `Beach::breeding_chance` for what happens as
+ * they fill up.
+ */
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BeachScenario {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub position: Position,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub capacity: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub overflow: Overflow,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub crowding_penalty: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub crabs: Vec<CrabScenario>,
+}
+
+/**
+ * A crab, and the indices (into `Scenario::reefs`) of the reefs it has discovered.
+ * Every reef must be within the crab's range of its beach (see `Crab::range`).
+ *
+ * Crabs eat only their `diet`, unless given a set of weighted `diets` such as
+ * `{ "Shellfish": 2, "Plants": 1 }`.
+ */
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CrabScenario {
+    pub name: String,
+    pub speed: u32,
+    pub color: Color,
+    pub diet: Diet,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub diets: Option<DietSet>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reefs: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScenarioError {
+    /** The named crab refers to a reef index that is not in the scenario. */
+    UnknownReef { crab: String, reef: usize },
+    /** The named crab refers to a reef that is out of its range. */
+    UnreachableReef { crab: String, reef: usize },
+    /** The beach at this index lists more crabs than its capacity. */
+    OverCapacity { beach: usize, capacity: usize },
+    /** A sweep asked for this many reefs, but the base scenario has none to repeat. */
+    NoReefsToRepeat { reef_count: usize },
+}
+
+impl fmt::Display for ScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScenarioError::UnknownReef { crab, reef } => {
+                write!(f, "crab {} refers to unknown reef {}", crab, reef)
+            }
+            ScenarioError::UnreachableReef { crab, reef } => {
+                write!(f, "reef {} is out of crab {}'s range", reef, crab)
+            }
+            ScenarioError::OverCapacity { beach, capacity } => {
+                write!(
+                    f,
+                    "beach {} has more than its capacity of {} crabs",
+                    beach, capacity
+                )
+            }
+            ScenarioError::NoReefsToRepeat { reef_count } => write!(
+                f,
+                "cannot make {} reefs from a scenario with no reefs",
+                reef_count
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ScenarioError {}
+
+impl Scenario {
+    /**
+     * Parses a scenario from JSON.
+     */
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<Scenario, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /**
+     * Builds a fresh ocean from this scenario, seeded with the scenario's seed.
+     */
+    pub fn build(&self) -> Result<Ocean, ScenarioError> {
+        let mut ocean = Ocean::with_seed(self.seed);
+        ocean.set_prey_params(self.prey_params);
+        ocean.set_exploration(self.exploration);
+        ocean.set_migration(self.migration);
+        ocean.set_lifespan(self.lifespan);
+        let reefs: Vec<_> = self
+            .reefs
+            .iter()
+            .map(|r| {
+                let reef = ocean.generate_reef(r.minnows, r.shrimp, r.clams, r.algae);
+                shared::write(&reef).set_position(r.position);
+                reef
+            })
+            .collect();
+
+        for (index, b) in self.beaches.iter().enumerate() {
+            if let Some(capacity) = b.capacity.filter(|&c| b.crabs.len() > c) {
+                return Err(ScenarioError::OverCapacity {
+                    beach: index,
+                    capacity,
+                });
+            }
+            let mut beach = ocean.new_beach();
+            beach.set_position(b.position);
+            beach.set_capacity(b.capacity);
+            beach.set_overflow(b.overflow);
+            beach.set_crowding_penalty(b.crowding_penalty);
+            for c in &b.crabs {
+                let mut crab = Crab::new(c.name.clone(), c.speed, c.color.clone(), c.diet);
+                crab.home = b.position;
+                if let Some(diets) = c.diets {
+                    crab.set_diets(diets);
+                }
+                for &i in &c.reefs {
+                    let reef = reefs.get(i).ok_or_else(|| ScenarioError::UnknownReef {
+                        crab: c.name.clone(),
+                        reef: i,
+                    })?;
+                    crab.try_discover_reef(SharedReef::clone(reef))
+                        .map_err(|_| ScenarioError::UnreachableReef {
+                            crab: c.name.clone(),
+                            reef: i,
+                        })?;
+                }
+                beach.add_crab(crab);
+            }
+            ocean.add_beach(beach);
+        }
+
+        Ok(ocean)
+    }
+}
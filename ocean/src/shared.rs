@@ -0,0 +1,93 @@
+//! Shared, mutable ownership of values (such as reefs) by several owners.
+//!
+//! By default, shared values are `Rc<RefCell<T>>`. With the `sync` feature they
+//! are `Arc<Mutex<T>>` instead, so that they (and the oceans, beaches and crabs
+//! holding them) can be sent and shared between threads. Code in this crate goes
+//! through the functions in this module so that it works either way.
+
+use crate::reef::Reef;
+
+#[cfg(not(feature = "sync"))]
+mod imp {
+    use std::cell::{Ref, RefCell, RefMut};
+    use std::rc::Rc;
+
+    pub type Shared<T> = Rc<RefCell<T>>;
+    pub type ReadGuard<'a, T> = Ref<'a, T>;
+    pub type WriteGuard<'a, T> = RefMut<'a, T>;
+
+    pub fn share<T>(value: T) -> Shared<T> {
+        Rc::new(RefCell::new(value))
+    }
+
+    pub fn read<T>(shared: &Shared<T>) -> ReadGuard<'_, T> {
+        shared.borrow()
+    }
+
+    pub fn write<T>(shared: &Shared<T>) -> WriteGuard<'_, T> {
+        shared.borrow_mut()
+    }
+
+    pub fn ptr_eq<T>(a: &Shared<T>, b: &Shared<T>) -> bool {
+        Rc::ptr_eq(a, b)
+    }
+
+    /**
+     * Implemented by every type. Without the `sync` feature, shared values do not
+     * need to be sent between threads.
+     */
+    pub trait MaybeSend {}
+
+    impl<T: ?Sized> MaybeSend for T {}
+}
+
+#[cfg(feature = "sync")]
+mod imp {
+    use std::sync::{Arc, Mutex, MutexGuard};
+
+    pub type Shared<T> = Arc<Mutex<T>>;
+    pub type ReadGuard<'a, T> = MutexGuard<'a, T>;
+    pub type WriteGuard<'a, T> = MutexGuard<'a, T>;
+
+    pub fn share<T>(value: T) -> Shared<T> {
+        Arc::new(Mutex::new(value))
+    }
+
+    pub fn read<T>(shared: &Shared<T>) -> ReadGuard<'_, T> {
+        shared.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn write<T>(shared: &Shared<T>) -> WriteGuard<'_, T> {
+        shared.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn ptr_eq<T>(a: &Shared<T>, b: &Shared<T>) -> bool {
+        Arc::ptr_eq(a, b)
+    }
+
+    /**
+     * Returns the address of a shared value, which identifies it among other shared values.
+     */
+    pub fn addr<T>(shared: &Shared<T>) -> usize {
+        Arc::as_ptr(shared) as *const () as usize
+    }
+
+    /**
+     * Implemented by every type that can be sent and shared between threads.
+     * With the `sync` feature, every `Prey` and `HuntStrategy` must be `Send +
+     * Sync`, so that oceans (and the crabs and reefs in them) are too.
+     */
+    pub trait MaybeSend: Send + Sync {}
+
+    impl<T: Send + Sync + ?Sized> MaybeSend for T {}
+}
+
+pub use imp::{ptr_eq, read, share, write, MaybeSend, ReadGuard, Shared, WriteGuard};
+
+#[cfg(feature = "sync")]
+pub(crate) use imp::addr;
+
+/**
+ * A reef shared between the ocean and the crabs that have discovered it.
+ */
+pub type SharedReef = Shared<Reef>;
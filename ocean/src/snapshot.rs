@@ -0,0 +1,402 @@
+//! Saving and loading a whole `Ocean`, as JSON or as a compact binary format.
+//!
+//! Two things in an ocean cannot be serialized directly:
+//!   - Reefs are shared between the ocean and its crabs through `SharedReef`s.
+//!     A snapshot stores every distinct reef once, and crabs refer to reefs by index.
+//!   - Prey are `Box<dyn Prey>` trait objects. A snapshot stores each as a tagged
+//!     `PreyRecord`, which a `PreyRegistry` turns back into `Prey` on load.
+//!     Crabs' `HuntStrategy`s are likewise stored as `StrategyRecord`s, which a
+//!     `StrategyRegistry` turns back into strategies. An ocean whose crabs use a
+//!     strategy missing from its `strategy_registry` cannot be saved.
+
+use crate::beach::{Beach, Overflow};
+use crate::cookbook::{Cookbook, CookbookError};
+use crate::crab::{Crab, CrabId};
+use crate::diet::DietSet;
+use crate::distribution::Distribution;
+use crate::exploration::ExplorationRates;
+use crate::genome::{Genome, MutationRates};
+use crate::grid::Position;
+use crate::migration::MigrationPolicy;
+use crate::ocean::Ocean;
+use crate::pantry::Pantry;
+use crate::pedigree::Pedigree;
+use crate::prey::{PreyParams, PreyRecord};
+use crate::rand::OceanRng;
+use crate::reef::Reef;
+use crate::registry::{PreyRegistry, StrategyRegistry};
+use crate::shared::{self, SharedReef};
+use crate::strategy::StrategyRecord;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Json(serde_json::Error),
+    Binary(bincode::Error),
+    /** A prey record could not be built by the registry. */
+    InvalidPrey(PreyRecord),
+    /** A crab refers to a reef index that is not in the snapshot. */
+    MissingReef(usize),
+    /** A strategy record could not be built by the strategy registry. */
+    InvalidStrategy(StrategyRecord),
+    /** Two crabs on the same beach have the same id. */
+    DuplicateCrabId(CrabId),
+    /** The snapshot's cookbook is not valid (see `Cookbook::validate`). */
+    InvalidCookbook(CookbookError),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SnapshotError::Json(e) => write!(f, "invalid JSON snapshot: {}", e),
+            SnapshotError::Binary(e) => write!(f, "invalid binary snapshot: {}", e),
+            SnapshotError::InvalidPrey(r) => write!(f, "cannot build prey from record {:?}", r),
+            SnapshotError::MissingReef(i) => write!(f, "snapshot has no reef with index {}", i),
+            SnapshotError::InvalidStrategy(r) => {
+                write!(f, "cannot build hunting strategy from record {:?}", r)
+            }
+            SnapshotError::DuplicateCrabId(id) => {
+                write!(f, "more than one crab on a beach has id {}", id)
+            }
+            SnapshotError::InvalidCookbook(e) => write!(f, "invalid cookbook: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+#[derive(Serialize, Deserialize)]
+struct OceanSnapshot {
+    tick: u64,
+    rng: OceanRng,
+    /** The id the ocean will give the next crab added to one of its beaches. */
+    next_crab_id: u64,
+    prey_params: PreyParams,
+    cookbook: Option<Cookbook>,
+    exploration: Option<ExplorationRates>,
+    migration: Option<MigrationPolicy>,
+    lifespan: Option<Distribution>,
+    beaches: Vec<BeachSnapshot>,
+    /** Every distinct reef, whether the ocean or only some crab refers to it. */
+    reefs: Vec<ReefSnapshot>,
+    /** Indices into `reefs` of the reefs owned by the ocean, in order. */
+    ocean_reefs: Vec<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ReefSnapshot {
+    capacity: usize,
+    position: Position,
+    prey: Vec<PreyRecord>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BeachSnapshot {
+    crabs: Vec<CrabSnapshot>,
+    rng: OceanRng,
+    mutation_rates: MutationRates,
+    pedigree: Pedigree,
+    capacity: Option<usize>,
+    overflow: Overflow,
+    crowding_penalty: f64,
+    position: Position,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CrabSnapshot {
+    id: CrabId,
+    parents: Option<(CrabId, CrabId)>,
+    name: String,
+    speed: u32,
+    genome: Genome,
+    diets: DietSet,
+    hungry: bool,
+    energy: u32,
+    age: u32,
+    lifespan: Option<u32>,
+    reefs: Vec<usize>,
+    reef_empty_ticks: Vec<u32>,
+    strategy: StrategyRecord,
+    pantry: PantrySnapshot,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PantrySnapshot {
+    capacity: usize,
+    shelf_life: u32,
+    /** Stored prey with their ages, oldest first. */
+    prey: Vec<(PreyRecord, u32)>,
+}
+
+/**
+ * Interns shared reefs, giving each distinct reef a single index.
+ */
+struct ReefTable {
+    reefs: Vec<SharedReef>,
+}
+
+impl ReefTable {
+    fn index_of(&mut self, reef: &SharedReef) -> usize {
+        match self.reefs.iter().position(|r| shared::ptr_eq(r, reef)) {
+            Some(i) => i,
+            None => {
+                self.reefs.push(SharedReef::clone(reef));
+                self.reefs.len() - 1
+            }
+        }
+    }
+}
+
+impl OceanSnapshot {
+    fn capture(ocean: &Ocean) -> Result<OceanSnapshot, SnapshotError> {
+        // Refuse to save what could not be loaded again.
+        for crab in ocean.beaches().flat_map(|b| b.crabs()) {
+            let record = crab.strategy().to_record();
+            if ocean.strategy_registry().build(&record).is_none() {
+                return Err(SnapshotError::InvalidStrategy(record));
+            }
+        }
+
+        let mut table = ReefTable { reefs: Vec::new() };
+        let ocean_reefs = ocean.reefs().map(|r| table.index_of(r)).collect();
+
+        let beaches = ocean
+            .beaches()
+            .map(|beach| BeachSnapshot {
+                crabs: beach
+                    .crabs()
+                    .map(|crab| CrabSnapshot {
+                        id: crab.id(),
+                        parents: crab.parents(),
+                        name: String::from(crab.name()),
+                        speed: crab.speed(),
+                        genome: crab.genome().clone(),
+                        diets: *crab.diets(),
+                        hungry: crab.is_hungry(),
+                        energy: crab.energy(),
+                        age: crab.age(),
+                        lifespan: crab.lifespan(),
+                        reefs: crab.reefs.iter().map(|r| table.index_of(r)).collect(),
+                        reef_empty_ticks: crab.reef_empty_ticks.clone(),
+                        strategy: crab.strategy().to_record(),
+                        pantry: PantrySnapshot {
+                            capacity: crab.pantry().capacity(),
+                            shelf_life: crab.pantry().shelf_life(),
+                            prey: crab
+                                .pantry()
+                                .prey_with_ages()
+                                .map(|(p, age)| (p.to_record(), age))
+                                .collect(),
+                        },
+                    })
+                    .collect(),
+                rng: beach.rng().clone(),
+                mutation_rates: *beach.mutation_rates(),
+                pedigree: beach.pedigree().clone(),
+                capacity: beach.capacity(),
+                overflow: beach.overflow(),
+                crowding_penalty: beach.crowding_penalty(),
+                position: beach.position(),
+            })
+            .collect();
+
+        let reefs = table
+            .reefs
+            .iter()
+            .map(|r| {
+                let reef = shared::read(r);
+                ReefSnapshot {
+                    capacity: reef.capacity(),
+                    position: reef.position(),
+                    prey: reef.prey().map(|p| p.to_record()).collect(),
+                }
+            })
+            .collect();
+
+        Ok(OceanSnapshot {
+            tick: ocean.tick(),
+            rng: ocean.rng().clone(),
+            next_crab_id: ocean.crab_ids.peek(),
+            prey_params: *ocean.prey_params(),
+            cookbook: ocean.cookbook().cloned(),
+            exploration: ocean.exploration().copied(),
+            migration: ocean.migration().copied(),
+            lifespan: ocean.lifespan().copied(),
+            beaches,
+            reefs,
+            ocean_reefs,
+        })
+    }
+
+    fn restore(
+        self,
+        registry: &PreyRegistry,
+        strategies: &StrategyRegistry,
+    ) -> Result<Ocean, SnapshotError> {
+        let mut reefs = Vec::new();
+        for r in self.reefs {
+            let mut reef = Reef::with_capacity(r.capacity);
+            reef.set_position(r.position);
+            for record in r.prey {
+                match registry.build(&record) {
+                    Some(prey) => reef.add_prey(prey),
+                    None => return Err(SnapshotError::InvalidPrey(record)),
+                }
+            }
+            reefs.push(shared::share(reef));
+        }
+        let reef_at = |i: usize| {
+            reefs
+                .get(i)
+                .map(SharedReef::clone)
+                .ok_or(SnapshotError::MissingReef(i))
+        };
+
+        if let Some(cookbook) = &self.cookbook {
+            cookbook
+                .validate()
+                .map_err(SnapshotError::InvalidCookbook)?;
+        }
+
+        let mut ocean = Ocean::new();
+        *ocean.registry_mut() = registry.clone();
+        *ocean.strategy_registry_mut() = strategies.clone();
+        *ocean.rng_mut() = self.rng;
+        ocean.tick = self.tick;
+        ocean.crab_ids.reserve_to(self.next_crab_id);
+        ocean.set_prey_params(self.prey_params);
+        ocean.set_cookbook(self.cookbook);
+        ocean.set_exploration(self.exploration);
+        ocean.set_migration(self.migration);
+        ocean.set_lifespan(self.lifespan);
+        for i in self.ocean_reefs {
+            ocean.reefs.push(reef_at(i)?);
+        }
+
+        for b in self.beaches {
+            let mut beach = Beach::new();
+            beach.crab_ids = ocean.crab_ids.clone();
+            beach.set_rng(b.rng);
+            beach.set_mutation_rates(b.mutation_rates);
+            beach.set_position(b.position);
+            for c in b.crabs {
+                let mut crab = Crab::from_genome(c.name, c.speed, c.genome);
+                crab.id = c.id;
+                crab.parents = c.parents;
+                crab.set_diets(c.diets);
+                crab.hungry = c.hungry;
+                crab.energy = c.energy;
+                crab.age = c.age;
+                crab.set_lifespan(c.lifespan);
+                match strategies.build(&c.strategy) {
+                    Some(s) => crab.set_strategy(s),
+                    None => return Err(SnapshotError::InvalidStrategy(c.strategy)),
+                }
+                for i in c.reefs {
+                    crab.discover_reef(reef_at(i)?);
+                }
+                for (ticks, &saved) in crab.reef_empty_ticks.iter_mut().zip(&c.reef_empty_ticks) {
+                    *ticks = saved;
+                }
+                // Restore every stored prey, even beyond a capacity that was lowered later.
+                let mut pantry = Pantry::with_capacity(usize::MAX);
+                pantry.set_shelf_life(c.pantry.shelf_life);
+                for (record, age) in c.pantry.prey {
+                    match registry.build(&record) {
+                        Some(prey) => pantry.add_prey_aged(prey, age),
+                        None => return Err(SnapshotError::InvalidPrey(record)),
+                    };
+                }
+                pantry.set_capacity(c.pantry.capacity);
+                *crab.pantry_mut() = pantry;
+                beach
+                    .try_add_crab(crab)
+                    .map_err(|_| SnapshotError::DuplicateCrabId(c.id))?;
+            }
+            beach.pedigree = b.pedigree;
+            beach.set_capacity(b.capacity);
+            beach.set_overflow(b.overflow);
+            beach.set_crowding_penalty(b.crowding_penalty);
+            ocean.beaches.push(beach);
+        }
+
+        Ok(ocean)
+    }
+}
+
+impl Ocean {
+    /**
+     * Saves this ocean as JSON. Returns an error if a crab hunts with a strategy
+     * that is not in this ocean's `strategy_registry`.
+     */
+    pub fn to_json(&self) -> Result<String, SnapshotError> {
+        serde_json::to_string(&OceanSnapshot::capture(self)?).map_err(SnapshotError::Json)
+    }
+
+    /**
+     * Loads an ocean saved with `to_json`. Prey and strategies are built with the
+     * default `PreyRegistry` and `StrategyRegistry`.
+     */
+    pub fn from_json(json: &str) -> Result<Ocean, SnapshotError> {
+        Ocean::from_json_with(json, &PreyRegistry::new())
+    }
+
+    /**
+     * Loads an ocean saved with `to_json`, building prey with the given registry,
+     * which the loaded ocean keeps (see `Ocean::registry`).
+     */
+    pub fn from_json_with(json: &str, registry: &PreyRegistry) -> Result<Ocean, SnapshotError> {
+        Ocean::from_json_with_strategies(json, registry, &StrategyRegistry::new())
+    }
+
+    /**
+     * Like `from_json_with`, also building hunting strategies with the given
+     * registry, which the loaded ocean keeps (see `Ocean::strategy_registry`).
+     */
+    pub fn from_json_with_strategies(
+        json: &str,
+        registry: &PreyRegistry,
+        strategies: &StrategyRegistry,
+    ) -> Result<Ocean, SnapshotError> {
+        let snapshot: OceanSnapshot = serde_json::from_str(json).map_err(SnapshotError::Json)?;
+        snapshot.restore(registry, strategies)
+    }
+
+    /**
+     * Saves this ocean in a compact binary format. Returns an error if a crab
+     * hunts with a strategy that is not in this ocean's `strategy_registry`.
+     */
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SnapshotError> {
+        bincode::serialize(&OceanSnapshot::capture(self)?).map_err(SnapshotError::Binary)
+    }
+
+    /**
+     * Loads an ocean saved with `to_bytes`. Prey and strategies are built with the
+     * default `PreyRegistry` and `StrategyRegistry`.
+     */
+    pub fn from_bytes(bytes: &[u8]) -> Result<Ocean, SnapshotError> {
+        Ocean::from_bytes_with(bytes, &PreyRegistry::new())
+    }
+
+    /**
+     * Loads an ocean saved with `to_bytes`, building prey with the given registry,
+     * which the loaded ocean keeps (see `Ocean::registry`).
+     */
+    pub fn from_bytes_with(bytes: &[u8], registry: &PreyRegistry) -> Result<Ocean, SnapshotError> {
+        Ocean::from_bytes_with_strategies(bytes, registry, &StrategyRegistry::new())
+    }
+
+    /**
+     * Like `from_bytes_with`, also building hunting strategies with the given
+     * registry, which the loaded ocean keeps (see `Ocean::strategy_registry`).
+     */
+    pub fn from_bytes_with_strategies(
+        bytes: &[u8],
+        registry: &PreyRegistry,
+        strategies: &StrategyRegistry,
+    ) -> Result<Ocean, SnapshotError> {
+        let snapshot: OceanSnapshot = bincode::deserialize(bytes).map_err(SnapshotError::Binary)?;
+        snapshot.restore(registry, strategies)
+    }
+}
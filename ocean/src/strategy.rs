@@ -0,0 +1,254 @@
+use crate::registry::StrategyRegistry;
+use crate::shared::MaybeSend;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/**
+ * How a crab decides which of its reefs to hunt in.
+ *
+ * On every attempt during a hunt, the crab asks its strategy to pick one of its
+ * reefs, given how much prey is currently left in each. The crab then tries to
+ * catch the prey at the front of that reef (see `Crab::hunt`).
+ *
+ * A hunt ends as soon as the crab catches something, when every reef is empty,
+ * or when the strategy gives up by returning None (or picking an empty reef).
+ *
+ * With the `sync` feature, all strategies must be `Send + Sync`, so that crabs
+ * can be shared between threads.
+ */
+pub trait HuntStrategy: fmt::Debug + MaybeSend {
+    /**
+     * Returns the index of the reef to try next, or None to give up.
+     *
+     * `populations` holds the number of prey left in each of the crab's reefs,
+     * in discovery order, and `attempts` is the number of prey the crab has
+     * already tried (and failed) to catch this hunt.
+     */
+    fn choose_reef(&mut self, populations: &[usize], attempts: u32) -> Option<usize>;
+
+    /**
+     * Called when the crab catches prey from the reef at the given index.
+     */
+    fn record_catch(&mut self, _reef: usize) {}
+
+    /**
+     * Called when the crab forgets the reef at the given index: reefs after it
+     * move down by one index.
+     */
+    fn forget_reef(&mut self, _reef: usize) {}
+
+    /**
+     * Describes this strategy as plain data, so that it can be saved and
+     * restored with `from_record`.
+     */
+    fn to_record(&self) -> StrategyRecord;
+}
+
+/**
+ * A plain-data description of a `HuntStrategy`, used to save and restore trait objects.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StrategyRecord {
+    tag: String,
+    params: BTreeMap<String, u32>,
+}
+
+impl StrategyRecord {
+    pub fn new(tag: &str) -> StrategyRecord {
+        StrategyRecord {
+            tag: String::from(tag),
+            params: BTreeMap::new(),
+        }
+    }
+
+    /**
+     * Returns this record with the named parameter set to `value`.
+     */
+    pub fn with(mut self, param: &str, value: u32) -> StrategyRecord {
+        self.params.insert(String::from(param), value);
+        self
+    }
+
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    pub fn get(&self, param: &str) -> Option<u32> {
+        self.params.get(param).copied()
+    }
+}
+
+/**
+ * Builds one of the built-in strategies from its record, or returns None if the
+ * record does not describe one. Custom strategies can be built with a
+ * `StrategyRegistry` instead.
+ */
+pub fn from_record(record: &StrategyRecord) -> Option<Box<dyn HuntStrategy>> {
+    StrategyRegistry::new().build(record)
+}
+
+/**
+ * Returns the index of the first non-empty reef at or after `start`, wrapping around.
+ */
+fn first_stocked(populations: &[usize], start: usize) -> Option<usize> {
+    let n = populations.len();
+    (0..n)
+        .map(|i| (start + i) % n)
+        .find(|&i| populations[i] > 0)
+}
+
+/**
+ * Always hunts in the first non-empty reef, in the order the crab discovered them.
+ *
+ * This is the default strategy.
+ */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InOrder;
+
+impl HuntStrategy for InOrder {
+    fn choose_reef(&mut self, populations: &[usize], _attempts: u32) -> Option<usize> {
+        first_stocked(populations, 0)
+    }
+
+    fn to_record(&self) -> StrategyRecord {
+        StrategyRecord::new("in_order")
+    }
+}
+
+/**
+ * Takes turns between reefs: each attempt (across hunts) moves on to the next
+ * non-empty reef after the one tried last.
+ */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RoundRobin {
+    pub(crate) next: usize,
+}
+
+impl RoundRobin {
+    pub fn new() -> RoundRobin {
+        RoundRobin { next: 0 }
+    }
+}
+
+impl HuntStrategy for RoundRobin {
+    fn choose_reef(&mut self, populations: &[usize], _attempts: u32) -> Option<usize> {
+        let reef = first_stocked(populations, self.next)?;
+        self.next = (reef + 1) % populations.len();
+        Some(reef)
+    }
+
+    fn forget_reef(&mut self, reef: usize) {
+        if self.next > reef {
+            self.next -= 1;
+        }
+    }
+
+    fn to_record(&self) -> StrategyRecord {
+        StrategyRecord::new("round_robin").with("next", self.next as u32)
+    }
+}
+
+/**
+ * Always hunts in the reef with the most prey left. Ties go to the reef
+ * discovered first.
+ */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RichestFirst;
+
+impl HuntStrategy for RichestFirst {
+    fn choose_reef(&mut self, populations: &[usize], _attempts: u32) -> Option<usize> {
+        populations
+            .iter()
+            .enumerate()
+            .filter(|(_, &n)| n > 0)
+            .min_by_key(|(_, &n)| std::cmp::Reverse(n))
+            .map(|(i, _)| i)
+    }
+
+    fn to_record(&self) -> StrategyRecord {
+        StrategyRecord::new("richest_first")
+    }
+}
+
+/**
+ * Goes back to the reef of its last successful hunt for as long as it has prey,
+ * then falls back to hunting in discovery order.
+ */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RememberSuccess {
+    pub(crate) last: Option<usize>,
+}
+
+impl RememberSuccess {
+    pub fn new() -> RememberSuccess {
+        RememberSuccess { last: None }
+    }
+
+    /**
+     * Returns the index of the reef this strategy last caught prey in, if any.
+     */
+    pub fn last(&self) -> Option<usize> {
+        self.last
+    }
+}
+
+impl HuntStrategy for RememberSuccess {
+    fn choose_reef(&mut self, populations: &[usize], _attempts: u32) -> Option<usize> {
+        match self.last {
+            Some(i) if populations.get(i).is_some_and(|&n| n > 0) => Some(i),
+            _ => first_stocked(populations, 0),
+        }
+    }
+
+    fn record_catch(&mut self, reef: usize) {
+        self.last = Some(reef);
+    }
+
+    fn forget_reef(&mut self, reef: usize) {
+        self.last = match self.last {
+            Some(i) if i == reef => None,
+            Some(i) if i > reef => Some(i - 1),
+            last => last,
+        };
+    }
+
+    fn to_record(&self) -> StrategyRecord {
+        match self.last {
+            Some(i) => StrategyRecord::new("remember_success").with("last", i as u32),
+            None => StrategyRecord::new("remember_success"),
+        }
+    }
+}
+
+/**
+ * Hunts in discovery order, but gives up after failing to catch `attempts` prey
+ * in a single hunt.
+ */
+#[derive(Debug, Clone, Copy)]
+pub struct GiveUpAfter {
+    attempts: u32,
+}
+
+impl GiveUpAfter {
+    pub fn new(attempts: u32) -> GiveUpAfter {
+        GiveUpAfter { attempts }
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+}
+
+impl HuntStrategy for GiveUpAfter {
+    fn choose_reef(&mut self, populations: &[usize], attempts: u32) -> Option<usize> {
+        if attempts >= self.attempts {
+            return None;
+        }
+        first_stocked(populations, 0)
+    }
+
+    fn to_record(&self) -> StrategyRecord {
+        StrategyRecord::new("give_up_after").with("attempts", self.attempts)
+    }
+}
@@ -0,0 +1,247 @@
+use crate::prey::PreyParams;
+use crate::scenario::{Scenario, ScenarioError};
+use crate::shared;
+
+/**
+ * Runs a base scenario under every combination of a set of parameters, with
+ * several seeds each, and aggregates the results into a `SweepTable`.
+ *
+ * The swept parameters are:
+ *   - `minnow_speeds`: the speed of generated minnows (see `PreyParams`).
+ *   - `shrimp_energies`: the energy of generated shrimp (see `PreyParams`).
+ *   - `reef_counts`: the number of reefs. The base scenario's reefs are repeated
+ *     in order until there are enough, and a crab that has discovered a reef in
+ *     the base scenario discovers every copy of it. A base scenario without reefs
+ *     can only be swept over zero reefs.
+ *
+ * Parameters that are not set keep the value from the base scenario.
+ */
+#[derive(Debug, Clone)]
+pub struct Sweep {
+    base: Scenario,
+    ticks: u64,
+    seeds: Vec<u64>,
+    minnow_speeds: Vec<u32>,
+    shrimp_energies: Vec<u32>,
+    reef_counts: Vec<usize>,
+}
+
+/**
+ * The aggregated results of every run of one combination of parameters in a `Sweep`.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepRow {
+    pub minnow_speed: u32,
+    pub shrimp_energy: u32,
+    pub reef_count: usize,
+    /** The number of runs (one per seed). */
+    pub runs: usize,
+    /** The mean fraction of crabs still alive at the end of a run. */
+    pub survival: f64,
+    /** The mean number of prey left in all reefs at the end of a run. */
+    pub prey_remaining: f64,
+    /** The fraction of all hunts, over all runs, that caught something. */
+    pub hunt_success: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepTable {
+    rows: Vec<SweepRow>,
+}
+
+impl Sweep {
+    /**
+     * Creates a sweep that runs `base` for `ticks` ticks, with only the base scenario's
+     * own seed and parameters until others are given.
+     */
+    pub fn new(base: Scenario, ticks: u64) -> Sweep {
+        Sweep {
+            seeds: vec![base.seed],
+            minnow_speeds: vec![base.prey_params.minnow_speed],
+            shrimp_energies: vec![base.prey_params.shrimp_energy],
+            reef_counts: vec![base.reefs.len()],
+            base,
+            ticks,
+        }
+    }
+
+    pub fn seeds(mut self, seeds: impl IntoIterator<Item = u64>) -> Sweep {
+        self.seeds = seeds.into_iter().collect();
+        self
+    }
+
+    pub fn minnow_speeds(mut self, speeds: impl IntoIterator<Item = u32>) -> Sweep {
+        self.minnow_speeds = speeds.into_iter().collect();
+        self
+    }
+
+    pub fn shrimp_energies(mut self, energies: impl IntoIterator<Item = u32>) -> Sweep {
+        self.shrimp_energies = energies.into_iter().collect();
+        self
+    }
+
+    pub fn reef_counts(mut self, counts: impl IntoIterator<Item = usize>) -> Sweep {
+        self.reef_counts = counts.into_iter().collect();
+        self
+    }
+
+    /**
+     * Returns the scenario for one combination of parameters and seed.
+     */
+    pub fn scenario(
+        &self,
+        minnow_speed: u32,
+        shrimp_energy: u32,
+        reef_count: usize,
+        seed: u64,
+    ) -> Scenario {
+        let mut scenario = self.base.clone();
+        scenario.seed = seed;
+        scenario.prey_params = PreyParams {
+            minnow_speed,
+            shrimp_energy,
+        };
+
+        // Without base reefs there is nothing to repeat (see `run`).
+        let n_base = self.base.reefs.len();
+        if n_base > 0 {
+            scenario.reefs = self
+                .base
+                .reefs
+                .iter()
+                .cycle()
+                .take(reef_count)
+                .cloned()
+                .collect();
+            for crab in scenario.beaches.iter_mut().flat_map(|b| b.crabs.iter_mut()) {
+                crab.reefs = (0..reef_count)
+                    .filter(|i| crab.reefs.contains(&(i % n_base)))
+                    .collect();
+            }
+        }
+        scenario
+    }
+
+    /**
+     * Runs every combination of parameters with every seed.
+     *
+     * With the `sync` feature, combinations run in parallel. Returns an error if
+     * any scenario is invalid, or if a reef count other than zero is swept over
+     * a base scenario with no reefs.
+     */
+    pub fn run(&self) -> Result<SweepTable, ScenarioError> {
+        if self.base.reefs.is_empty() {
+            if let Some(&reef_count) = self.reef_counts.iter().find(|&&n| n > 0) {
+                return Err(ScenarioError::NoReefsToRepeat { reef_count });
+            }
+        }
+
+        let mut combinations = Vec::new();
+        for &minnow_speed in &self.minnow_speeds {
+            for &shrimp_energy in &self.shrimp_energies {
+                for &reef_count in &self.reef_counts {
+                    combinations.push((minnow_speed, shrimp_energy, reef_count));
+                }
+            }
+        }
+
+        #[cfg(feature = "sync")]
+        let rows = {
+            use rayon::prelude::*;
+            combinations
+                .into_par_iter()
+                .map(|(m, s, r)| self.run_combination(m, s, r))
+                .collect::<Result<Vec<_>, _>>()?
+        };
+        #[cfg(not(feature = "sync"))]
+        let rows = combinations
+            .into_iter()
+            .map(|(m, s, r)| self.run_combination(m, s, r))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(SweepTable { rows })
+    }
+
+    fn run_combination(
+        &self,
+        minnow_speed: u32,
+        shrimp_energy: u32,
+        reef_count: usize,
+    ) -> Result<SweepRow, ScenarioError> {
+        let mut survival = 0.0;
+        let mut prey_remaining = 0.0;
+        let mut fed = 0;
+        let mut hunts = 0;
+
+        for &seed in &self.seeds {
+            let mut ocean = self
+                .scenario(minnow_speed, shrimp_energy, reef_count, seed)
+                .build()?;
+            let crabs_before: usize = ocean.beaches().map(|b| b.size()).sum();
+
+            for report in ocean.run(self.ticks) {
+                fed += report.fed();
+                hunts += report.crabs();
+            }
+
+            let crabs_after: usize = ocean.beaches().map(|b| b.size()).sum();
+            if crabs_before > 0 {
+                survival += crabs_after as f64 / crabs_before as f64;
+            }
+            prey_remaining += ocean
+                .reefs()
+                .map(|r| shared::read(r).population())
+                .sum::<usize>() as f64;
+        }
+
+        let runs = self.seeds.len();
+        let mean = |total: f64| if runs > 0 { total / runs as f64 } else { 0.0 };
+        Ok(SweepRow {
+            minnow_speed,
+            shrimp_energy,
+            reef_count,
+            runs,
+            survival: mean(survival),
+            prey_remaining: mean(prey_remaining),
+            hunt_success: if hunts > 0 {
+                fed as f64 / hunts as f64
+            } else {
+                0.0
+            },
+        })
+    }
+}
+
+impl SweepTable {
+    /** The header row matching `to_csv`. */
+    pub const CSV_HEADER: &'static str =
+        "minnow_speed,shrimp_energy,reef_count,runs,survival,prey_remaining,hunt_success";
+
+    /**
+     * Returns one row per combination of parameters, in the order they were swept.
+     */
+    pub fn rows(&self) -> &[SweepRow] {
+        &self.rows
+    }
+
+    /**
+     * Formats the table as comma-separated values, with a header row.
+     */
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(SweepTable::CSV_HEADER);
+        csv.push('\n');
+        for row in &self.rows {
+            csv.push_str(&format!(
+                "{},{},{},{},{:.4},{:.2},{:.4}\n",
+                row.minnow_speed,
+                row.shrimp_energy,
+                row.reef_count,
+                row.runs,
+                row.survival,
+                row.prey_remaining,
+                row.hunt_success
+            ));
+        }
+        csv
+    }
+}
@@ -0,0 +1,148 @@
+extern crate ocean;
+
+/*
+ * Run `cargo test age_` to run all of the aging and lifespan tests.
+ */
+
+use ocean::beach::*;
+use ocean::color::*;
+use ocean::crab::*;
+use ocean::diet::*;
+use ocean::distribution::*;
+use ocean::ocean::*;
+use ocean::prey::*;
+
+/* Fixtures */
+
+fn new_crab(name: &str, lifespan: u32) -> Crab {
+    Crab::new(String::from(name), 20, Color::new_blue(), Diet::Fish).with_lifespan(lifespan)
+}
+
+/* Tests */
+
+#[test]
+fn age_slows_crabs_past_prime() {
+    let mut crab = new_crab("Gramps", 8);
+    for _ in 0..5 {
+        crab.grow_older();
+        crab.grow();
+    }
+    assert_eq!(crab.age(), 5);
+    assert!(!crab.is_past_prime());
+    assert_eq!(crab.speed(), 20);
+
+    // A minnow it could catch in its prime...
+    let mut minnow = Minnow::new(19);
+    assert!(!minnow.try_escape(&crab));
+
+    crab.grow_older();
+    assert!(crab.is_past_prime());
+    crab.grow();
+    crab.grow();
+    assert_eq!(crab.speed(), 18);
+
+    // ...now gets away.
+    assert!(minnow.try_escape(&crab));
+}
+
+#[test]
+fn age_slowing_stops_at_one() {
+    let mut crab = new_crab("Ancient", 0);
+    for _ in 0..30 {
+        crab.grow();
+    }
+    assert_eq!(crab.speed(), 1);
+}
+
+#[test]
+fn age_crabs_without_lifespan_live_forever() {
+    let mut crab = Crab::new(String::from("Immortal"), 5, Color::new_red(), Diet::Fish);
+    for _ in 0..1000 {
+        crab.grow_older();
+    }
+    assert_eq!(crab.lifespan(), None);
+    assert!(!crab.is_past_prime());
+    assert_eq!(crab.cause_of_death(), None);
+}
+
+#[test]
+fn age_cause_of_death() {
+    let mut crab = new_crab("Old", 1);
+    assert_eq!(crab.cause_of_death(), None);
+    crab.grow_older();
+    assert!(crab.is_dead_of_old_age());
+    assert_eq!(crab.cause_of_death(), Some(DeathCause::OldAge));
+
+    // Starvation comes first.
+    for _ in 0..Crab::INITIAL_ENERGY {
+        crab.metabolize();
+    }
+    assert_eq!(crab.cause_of_death(), Some(DeathCause::Starvation));
+}
+
+#[test]
+fn age_beach_removes_dead_crabs() {
+    let mut beach = Beach::new();
+    beach.add_crab(new_crab("Young", 10));
+    beach.add_crab(new_crab("Old", 1));
+    let mut starving = new_crab("Starving", 10);
+    for _ in 0..Crab::INITIAL_ENERGY {
+        starving.metabolize();
+    }
+    beach.add_crab(starving);
+    let ids: Vec<CrabId> = beach.crabs().map(|c| c.id()).collect();
+    for crab in beach.crabs_mut() {
+        crab.grow_older();
+    }
+
+    assert_eq!(
+        beach.remove_dead(),
+        vec![
+            (ids[1], DeathCause::OldAge),
+            (ids[2], DeathCause::Starvation)
+        ]
+    );
+    assert_eq!(beach.size(), 1);
+    assert_eq!(beach.pedigree().cause_of_death(ids[0]), None);
+    assert_eq!(
+        beach.pedigree().cause_of_death(ids[1]),
+        Some(DeathCause::OldAge)
+    );
+    assert_eq!(
+        beach.pedigree().cause_of_death(ids[2]),
+        Some(DeathCause::Starvation)
+    );
+}
+
+#[test]
+fn age_ocean_draws_lifespans() {
+    let mut ocean = Ocean::new();
+    ocean.set_lifespan(Some(Distribution::Uniform { min: 2, max: 4 }));
+    let mut beach = Beach::new();
+    for i in 0..10 {
+        beach.add_crab(Crab::new(
+            format!("Crab {}", i),
+            20,
+            Color::new_red(),
+            Diet::Plants,
+        ));
+    }
+    beach.add_crab(new_crab("Given", 100));
+    ocean.add_beach(beach);
+
+    ocean.step();
+    let beach = ocean.beaches().next().unwrap();
+    for crab in beach.crabs() {
+        assert_eq!(crab.age(), 1);
+        let lifespan = crab.lifespan().unwrap();
+        if crab.name() == "Given" {
+            assert_eq!(lifespan, 100);
+        } else {
+            assert!((2..=4).contains(&lifespan));
+        }
+    }
+
+    let died: usize = ocean.run(4).iter().map(|r| r.died_of_old_age()).sum();
+    assert_eq!(died, 10);
+    assert_eq!(ocean.beaches().next().unwrap().size(), 1);
+}
@@ -0,0 +1,175 @@
+#![cfg(feature = "serde")]
+
+extern crate ocean;
+
+/*
+ * Run `cargo test cookbook_` to run all of the cookbook loading tests.
+ */
+
+use ocean::color::*;
+use ocean::cookbook::*;
+use ocean::crab::*;
+use ocean::diet::*;
+use ocean::prey::*;
+
+/* Fixtures */
+
+fn example_toml() -> String {
+    std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/cookbooks/example.toml"
+    ))
+    .unwrap()
+}
+
+/* Tests */
+
+#[test]
+fn cookbook_from_toml() {
+    let cookbook = Cookbook::from_toml(&example_toml()).unwrap();
+    assert_eq!(cookbook.recipes().len(), 4);
+
+    let paella = cookbook.recipe("paella").unwrap();
+    assert_eq!(paella.diets(), &[Diet::Fish, Diet::Shellfish]);
+    assert_eq!(
+        paella.ingredients(),
+        &[
+            Ingredient::new(PreyKind::Minnow, 1),
+            Ingredient::new(PreyKind::Shrimp, 2),
+            Ingredient::new(PreyKind::Clam, 2),
+        ]
+    );
+    assert!(paella.has_tag("festive"));
+    assert_eq!(paella.min_freshness(), 50);
+    assert!(!paella.has_tag("soup"));
+}
+
+#[test]
+fn cookbook_from_json() {
+    let json = r#"{
+        "recipes": [
+            {
+                "name": "kelp wrap",
+                "diets": ["Plants"],
+                "ingredients": [{ "prey": "kelp", "quantity": 4 }]
+            }
+        ]
+    }"#;
+    let cookbook = Cookbook::from_json(json).unwrap();
+    let wrap = cookbook.recipe("kelp wrap").unwrap();
+    assert_eq!(wrap.diet(), Some(Diet::Plants));
+    assert_eq!(
+        wrap.ingredients()[0].prey(),
+        &PreyKind::Custom(String::from("kelp"))
+    );
+    assert!(wrap.tags().is_empty());
+}
+
+#[test]
+fn cookbook_malformed() {
+    assert!(matches!(
+        Cookbook::from_toml("[[recipes]]\nname = 3"),
+        Err(CookbookError::Toml(_))
+    ));
+    assert!(matches!(
+        Cookbook::from_json("{ \"recipes\": [{ \"name\": \"stew\" }] }"),
+        Err(CookbookError::Json(_))
+    ));
+}
+
+#[test]
+fn cookbook_validation() {
+    let err = Cookbook::from_toml("[[recipes]]\nname = \"\"\ndiets = [\"Fish\"]").unwrap_err();
+    assert!(matches!(err, CookbookError::UnnamedRecipe { index: 0 }));
+
+    let err = Cookbook::from_toml(
+        "[[recipes]]\nname = \"stew\"\ndiets = [\"Fish\"]\n\
+         [[recipes]]\nname = \"stew\"\ndiets = [\"Plants\"]",
+    )
+    .unwrap_err();
+    assert!(matches!(err, CookbookError::DuplicateRecipe { name } if name == "stew"));
+
+    let err = Cookbook::from_toml("[[recipes]]\nname = \"stew\"\ndiets = []").unwrap_err();
+    assert!(matches!(err, CookbookError::NoDiets { recipe } if recipe == "stew"));
+
+    let err = Cookbook::from_toml(
+        "[[recipes]]\nname = \"stew\"\ndiets = [\"Fish\"]\n\
+         ingredients = [{ prey = \"minnow\", quantity = 0 }]",
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "recipe stew calls for no Minnow");
+
+    let err = Cookbook::from_toml(
+        "[[recipes]]\nname = \"stew\"\ndiets = [\"Shellfish\"]\n\
+         ingredients = [{ prey = \"clam\", quantity = 2 }, { prey = \"clam\", quantity = 1 }]",
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        CookbookError::DuplicateIngredient { recipe, prey: PreyKind::Clam } if recipe == "stew"
+    ));
+
+    let err = Cookbook::from_toml(
+        "[[recipes]]\nname = \"stew\"\ndiets = [\"Fish\"]\nmin_freshness = 101",
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        CookbookError::ImpossibleFreshness {
+            min_freshness: 101,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn cookbook_choose_multi_diet_recipe() {
+    let cookbook = Cookbook::from_toml(
+        "[[recipes]]\nname = \"paella\"\ndiets = [\"Fish\", \"Shellfish\"]\n\
+         [[recipes]]\nname = \"chowder\"\ndiets = [\"Shellfish\"]",
+    )
+    .unwrap();
+
+    // Paella needs both fish and shellfish...
+    let crab = Crab::new(String::from("Prinz"), 20, Color::new_red(), Diet::Shellfish);
+    assert_eq!(crab.choose_recipe(&cookbook).unwrap().name(), "chowder");
+
+    let crab = crab.with_diets(Diet::Shellfish | Diet::Fish);
+    assert_eq!(crab.choose_recipe(&cookbook).unwrap().name(), "paella");
+
+    // ...and is only as appealing as the crab's least favorite of the two.
+    let crab = crab.with_diets(DietSet::only(Diet::Fish).with(Diet::Shellfish, 2));
+    assert_eq!(crab.choose_recipe(&cookbook).unwrap().name(), "chowder");
+}
+
+#[test]
+fn cookbook_from_recipes() {
+    let chowder = Recipe::new(String::from("chowder"), Diet::Shellfish);
+    let cookbook = Cookbook::from_recipes(vec![chowder.clone()]).unwrap();
+    assert_eq!(
+        cookbook.recipe("chowder").unwrap().diet(),
+        Some(Diet::Shellfish)
+    );
+
+    let err = Cookbook::from_recipes(vec![chowder.clone(), chowder]).unwrap_err();
+    assert!(matches!(err, CookbookError::DuplicateRecipe { name } if name == "chowder"));
+
+    let stew = Recipe::new(String::from("stew"), Diet::Fish).with_ingredient(PreyKind::Minnow, 0);
+    let err = Cookbook::from_recipes(vec![stew]).unwrap_err();
+    assert!(matches!(err, CookbookError::EmptyIngredient { .. }));
+}
+
+#[test]
+fn cookbook_unvalidated_recipe_without_diets() {
+    // Deserializing directly skips validation, so the recipe has no diets at all.
+    let cookbook: Cookbook =
+        serde_json::from_str(r#"{"recipes": [{"name": "air", "diets": []}]}"#).unwrap();
+    let air = cookbook.recipe("air").unwrap();
+    assert_eq!(air.diet(), None);
+
+    let crab = Crab::new(String::from("Prinz"), 20, Color::new_red(), Diet::Fish);
+    assert!(crab.choose_recipe(&cookbook).is_none());
+    assert!(crab
+        .choose_recipes(&cookbook, &RecipeScoring::default())
+        .is_empty());
+}
@@ -0,0 +1,205 @@
+extern crate ocean;
+
+/*
+ * Run `cargo test cook_` to run all of the cooking tests.
+ */
+
+use ocean::beach::*;
+use ocean::color::*;
+use ocean::cookbook::*;
+use ocean::crab::*;
+use ocean::diet::*;
+use ocean::error::*;
+use ocean::ocean::*;
+use ocean::prey::*;
+
+/* Fixtures */
+
+fn new_omnivore() -> Crab {
+    Crab::new(String::from("Omni"), 20, Color::new_red(), Diet::Fish)
+        .with_diets(DietSet::omnivore())
+}
+
+fn names(ranked: &[(&Recipe, f64)]) -> Vec<String> {
+    ranked.iter().map(|(r, _)| r.name().clone()).collect()
+}
+
+/* Tests */
+
+#[test]
+fn cook_choose_recipes_ranked() {
+    let cookbook = Cookbook::new();
+    let scoring = RecipeScoring::default();
+    let mut crab = new_omnivore();
+
+    // With an empty pantry, the more popular chowder wins.
+    let ranked = crab.choose_recipes(&cookbook, &scoring);
+    assert_eq!(names(&ranked), vec!["chowder", "cioppino"]);
+    assert_eq!(ranked[0].1, 1.5);
+
+    // With the minnows for cioppino at hand, cioppino wins.
+    crab.pantry_mut().add_prey(Box::new(Minnow::new(25)));
+    crab.pantry_mut().add_prey(Box::new(Minnow::new(25)));
+    let ranked = crab.choose_recipes(&cookbook, &scoring);
+    assert_eq!(names(&ranked), vec!["cioppino", "chowder"]);
+    assert!((ranked[0].1 - 2.3).abs() < 1e-9);
+
+    // Half of the ingredients are worth half as much.
+    crab.pantry_mut().take(&PreyKind::Minnow);
+    let ranked = crab.choose_recipes(&cookbook, &scoring);
+    assert_eq!(names(&ranked), vec!["cioppino", "chowder"]);
+    assert!((ranked[0].1 - 1.8).abs() < 1e-9);
+}
+
+#[test]
+fn cook_choose_recipes_by_diet() {
+    let cookbook = Cookbook::new();
+    let scoring = RecipeScoring::default();
+
+    let crab = Crab::new(String::from("Pinchy"), 20, Color::new_red(), Diet::Fish);
+    assert_eq!(
+        names(&crab.choose_recipes(&cookbook, &scoring)),
+        vec!["cioppino"]
+    );
+
+    let crab = crab.with_diets(DietSet::only(Diet::Plants));
+    assert!(crab.choose_recipes(&cookbook, &scoring).is_empty());
+
+    // Diet preferences can outweigh popularity.
+    let crab = new_omnivore().with_diets(DietSet::omnivore().with(Diet::Fish, 2));
+    assert_eq!(
+        names(&crab.choose_recipes(&cookbook, &scoring)),
+        vec!["cioppino", "chowder"]
+    );
+}
+
+#[test]
+fn cook_consumes_pantry() {
+    let cookbook = Cookbook::new();
+    let chowder = cookbook.recipe("chowder").unwrap();
+    let mut crab = new_omnivore();
+    for _ in 0..3 {
+        crab.pantry_mut().add_prey(Box::new(Clam::new()));
+    }
+    crab.metabolize();
+
+    assert!(crab.can_cook(chowder));
+    assert_eq!(crab.cook(chowder), Ok(8));
+    assert_eq!(crab.energy(), Crab::INITIAL_ENERGY - Crab::METABOLISM + 8);
+    assert_eq!(crab.pantry().count(&PreyKind::Clam), 1);
+    assert!(!crab.can_cook(chowder));
+}
+
+#[test]
+fn cook_errors() {
+    let cookbook = Cookbook::new();
+    let chowder = cookbook.recipe("chowder").unwrap();
+
+    let mut crab = new_omnivore();
+    crab.pantry_mut().add_prey(Box::new(Clam::new()));
+    assert_eq!(
+        crab.cook(chowder),
+        Err(OceanError::MissingIngredient {
+            prey: PreyKind::Clam,
+            needed: 2,
+            available: 1
+        })
+    );
+    assert_eq!(crab.pantry().size(), 1);
+    assert_eq!(crab.energy(), Crab::INITIAL_ENERGY);
+
+    let mut crab = Crab::new(String::from("Pinchy"), 20, Color::new_red(), Diet::Fish);
+    crab.pantry_mut().add_prey(Box::new(Clam::new()));
+    crab.pantry_mut().add_prey(Box::new(Clam::new()));
+    assert_eq!(
+        crab.cook(chowder),
+        Err(OceanError::InedibleRecipe {
+            recipe: String::from("chowder")
+        })
+    );
+    assert_eq!(crab.pantry().size(), 2);
+}
+
+#[test]
+fn cook_same_prey_counts_for_one_ingredient() {
+    // Listing clams twice asks for three clams, not two.
+    let recipe = Recipe::new(String::from("double chowder"), Diet::Shellfish)
+        .with_ingredient(PreyKind::Clam, 2)
+        .with_ingredient(PreyKind::Clam, 1);
+    let mut crab = new_omnivore();
+    crab.pantry_mut().add_prey(Box::new(Clam::new()));
+    crab.pantry_mut().add_prey(Box::new(Clam::new()));
+
+    assert!(!crab.can_cook(&recipe));
+    assert_eq!(
+        crab.cook(&recipe),
+        Err(OceanError::MissingIngredient {
+            prey: PreyKind::Clam,
+            needed: 1,
+            available: 0
+        })
+    );
+    assert_eq!(crab.pantry().size(), 2);
+
+    crab.pantry_mut().add_prey(Box::new(Clam::new()));
+    assert!(crab.cook(&recipe).is_ok());
+    assert!(crab.pantry().is_empty());
+}
+
+#[test]
+fn cook_ocean_step_cooks_from_pantry() {
+    let energy_after_step = |cookbook: Option<Cookbook>| {
+        let mut ocean = Ocean::new();
+        ocean.set_cookbook(cookbook);
+        let mut crab = Crab::new(
+            String::from("Prinz"),
+            20,
+            Color::new_blue(),
+            Diet::Shellfish,
+        );
+        crab.discover_reef(ocean.generate_reef(0, 0, 1, 0));
+        crab.pantry_mut().add_prey(Box::new(Clam::new()));
+        crab.pantry_mut().add_prey(Box::new(Clam::new()));
+        let mut beach = Beach::new();
+        beach.add_crab(crab);
+        ocean.add_beach(beach);
+
+        ocean.step();
+        ocean.beaches().next().unwrap().get_crab(0).energy()
+    };
+
+    // After eating the clam it hunted, the crab cooks chowder from its pantry.
+    assert_eq!(energy_after_step(None), 13);
+    assert_eq!(
+        energy_after_step(Some(Cookbook::new())),
+        Crab::MAX_ENERGY - 1
+    );
+}
+
+#[test]
+fn cook_requires_min_freshness() {
+    let sashimi = Recipe::new(String::from("sashimi"), Diet::Fish)
+        .with_ingredient(PreyKind::Minnow, 1)
+        .with_min_freshness(90);
+    let mut crab = new_omnivore();
+    crab.pantry_mut().add_prey(Box::new(Minnow::new(25)));
+    crab.pantry_mut().age();
+
+    // A day-old minnow is too old for sashimi...
+    assert_eq!(crab.pantry().prey().next().unwrap().freshness(), 90);
+    assert!(crab.can_cook(&sashimi));
+    crab.pantry_mut().age();
+    assert_eq!(
+        crab.cook(&sashimi),
+        Err(OceanError::MissingIngredient {
+            prey: PreyKind::Minnow,
+            needed: 1,
+            available: 0
+        })
+    );
+
+    // ...but a freshly caught one is fine, and is the one that gets used.
+    crab.pantry_mut().add_prey(Box::new(Minnow::new(25)));
+    assert_eq!(crab.cook(&sashimi), Ok(5));
+    assert_eq!(crab.pantry().prey().next().unwrap().age(), 2);
+}
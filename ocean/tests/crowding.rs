@@ -0,0 +1,180 @@
+extern crate ocean;
+
+/*
+ * Run `cargo test crowd_` to run all of the beach capacity and crowding tests.
+ */
+
+use ocean::beach::*;
+use ocean::color::*;
+use ocean::crab::*;
+use ocean::diet::*;
+use ocean::error::*;
+use ocean::grid::*;
+use ocean::ocean::*;
+use ocean::rand::*;
+
+/* Fixtures */
+
+/**
+ * A crab that has burned `ticks` ticks' worth of energy.
+ */
+fn new_crab(name: &str, ticks: u32) -> Crab {
+    let mut crab = Crab::new(String::from(name), 20, Color::new_red(), Diet::Shellfish);
+    for _ in 0..ticks {
+        crab.metabolize();
+    }
+    crab
+}
+
+fn new_beach(capacity: usize, overflow: Overflow, crabs: usize) -> Beach {
+    let mut beach = Beach::with_capacity(capacity);
+    beach.set_overflow(overflow);
+    for i in 0..crabs {
+        beach.add_crab(new_crab(&format!("Crab {}", i), 0));
+    }
+    beach
+}
+
+fn names(beach: &Beach) -> Vec<&str> {
+    beach.crabs().map(|c| c.name()).collect()
+}
+
+/* Tests */
+
+#[test]
+fn crowd_reject_by_default() {
+    let mut beach = new_beach(1, Overflow::Reject, 1);
+    assert_eq!(Beach::new().overflow(), Overflow::Reject);
+    let err = beach.try_add_crab(new_crab("Late", 0)).unwrap_err();
+    assert_eq!(err.error, OceanError::CapacityExceeded { capacity: 1 });
+    assert_eq!(beach.size(), 1);
+
+    // The rejected crab is handed back, without having used up an id.
+    assert_eq!(err.crab.name(), "Late");
+    assert_eq!(err.crab.id(), CrabId::UNASSIGNED);
+    beach.set_capacity(Some(2));
+    beach.add_crab(*err.crab);
+    let never_full = new_beach(2, Overflow::Reject, 2);
+    let ids = |beach: &Beach| beach.crabs().map(|c| c.id()).collect::<Vec<_>>();
+    assert_eq!(ids(&beach), ids(&never_full));
+}
+
+#[test]
+fn crowd_evict_weakest() {
+    let mut beach = new_beach(2, Overflow::EvictWeakest, 0);
+    beach.add_crab(new_crab("Tired", 2));
+    beach.add_crab(new_crab("Strong", 0));
+
+    let evicted = beach.try_add_crab(new_crab("Newcomer", 1)).unwrap();
+    assert_eq!(evicted.unwrap().name(), "Tired");
+    assert_eq!(names(&beach), vec!["Strong", "Newcomer"]);
+
+    // A newcomer no stronger than anyone on the beach is turned away instead.
+    let evicted = beach.try_add_crab(new_crab("Weakling", 1)).unwrap();
+    assert_eq!(evicted.unwrap().name(), "Weakling");
+    assert_eq!(names(&beach), vec!["Strong", "Newcomer"]);
+}
+
+#[test]
+fn crowd_breeding_into_full_beach() {
+    let mut beach = new_beach(2, Overflow::EvictWeakest, 2);
+
+    // Babies are born no stronger than their parents, so there is no room for them...
+    let turned_away = beach.try_breed_crabs(0, 1, String::from("Baby")).unwrap();
+    assert_eq!(turned_away.unwrap().name(), "Baby");
+    assert_eq!(beach.size(), 2);
+    assert_eq!(beach.pedigree().size(), 2);
+
+    // ...unless a parent is weaker.
+    let mut beach = new_beach(2, Overflow::EvictWeakest, 0);
+    beach.add_crab(new_crab("Old", 5));
+    beach.add_crab(new_crab("Young", 0));
+    let evicted = beach.try_breed_crabs(0, 1, String::from("Baby")).unwrap();
+    assert_eq!(evicted.unwrap().name(), "Old");
+    assert_eq!(names(&beach), vec!["Young", "Baby"]);
+
+    // Beaches that let overflow migrate take babies in beyond their capacity.
+    let mut beach = new_beach(2, Overflow::Migrate, 2);
+    assert!(beach
+        .try_breed_crabs(0, 1, String::from("Baby"))
+        .unwrap()
+        .is_none());
+    assert_eq!(beach.size(), 3);
+}
+
+#[test]
+fn crowd_penalty_lowers_breeding_chance() {
+    let mut beach = new_beach(4, Overflow::Migrate, 2);
+    assert_eq!(beach.breeding_chance(), 1.0);
+    beach.set_crowding_penalty(1.0);
+    assert_eq!(beach.breeding_chance(), 0.5);
+
+    beach.add_crab(new_crab("Crab 2", 0));
+    beach.add_crab(new_crab("Crab 3", 0));
+    assert_eq!(beach.breeding_chance(), 0.0);
+    assert_eq!(
+        beach
+            .try_breed_crabs(0, 1, String::from("Baby"))
+            .unwrap_err(),
+        OceanError::CrowdedOut { size: 4 }
+    );
+
+    // Over many tries, about half of the crabs on a half-full beach manage to breed.
+    let mut bred = 0;
+    for seed in 0..200 {
+        let mut beach = new_beach(4, Overflow::Reject, 2);
+        beach.set_rng(OceanRng::new(seed));
+        beach.set_crowding_penalty(1.0);
+        if beach.try_breed_crabs(0, 1, String::from("Baby")).is_ok() {
+            bred += 1;
+        }
+    }
+    assert!((70..130).contains(&bred), "{} of 200 bred", bred);
+
+    // Unlimited beaches are never crowded.
+    let mut beach = Beach::new();
+    beach.set_crowding_penalty(1.0);
+    assert_eq!(beach.breeding_chance(), 1.0);
+}
+
+#[test]
+fn crowd_overflow_migrates_at_end_of_tick() {
+    let mut ocean = Ocean::new();
+    ocean.add_beach(new_beach(2, Overflow::Migrate, 2));
+    let mut near = new_beach(1, Overflow::Reject, 0);
+    near.set_position(Position::new(3, 0));
+    ocean.add_beach(near);
+    let mut far = Beach::new();
+    far.set_position(Position::new(10, 0));
+    ocean.add_beach(far);
+
+    let breed = |ocean: &mut Ocean, name: &str| {
+        let beach = ocean.beaches_mut().next().unwrap();
+        beach.try_breed_crabs(0, 1, String::from(name)).unwrap();
+        assert_eq!(beach.size(), 3);
+    };
+    let sizes = |ocean: &Ocean| ocean.beaches().map(|b| b.size()).collect::<Vec<_>>();
+
+    breed(&mut ocean, "First");
+    assert_eq!(ocean.step().migrated(), 1);
+    assert_eq!(sizes(&ocean), vec![2, 1, 0]);
+    assert_eq!(ocean.beaches().nth(1).unwrap().get_crab(0).name(), "First");
+
+    breed(&mut ocean, "Second");
+    assert_eq!(ocean.step().migrated(), 1);
+    assert_eq!(sizes(&ocean), vec![2, 1, 1]);
+}
+
+#[test]
+fn crowd_overflow_evicted_with_nowhere_to_go() {
+    let mut ocean = Ocean::new();
+    ocean.add_beach(new_beach(2, Overflow::Migrate, 2));
+    let beach = ocean.beaches_mut().next().unwrap();
+    beach.add_crab(new_crab("Latecomer", 0));
+
+    let report = ocean.step();
+    assert_eq!(report.migrated(), 0);
+    assert_eq!(report.evicted(), 1);
+    let beach = ocean.beaches().next().unwrap();
+    assert_eq!(names(beach), vec!["Crab 0", "Crab 1"]);
+}
@@ -0,0 +1,118 @@
+extern crate ocean;
+
+/*
+ * Run `cargo test diet_` to run all of the multi-diet tests.
+ */
+
+use ocean::color::*;
+use ocean::cookbook::*;
+use ocean::crab::*;
+use ocean::diet::*;
+use ocean::genome::*;
+use ocean::prey::*;
+use ocean::rand::*;
+use ocean::reef::*;
+use ocean::shared::*;
+
+/* Fixtures */
+
+fn new_crab(diet: Diet) -> Crab {
+    Crab::new(String::from("Omni"), 20, Color::new_red(), diet)
+}
+
+/* Tests */
+
+#[test]
+fn diet_set_operations() {
+    let set = Diet::Shellfish | Diet::Plants;
+    assert!(set.contains(Diet::Shellfish));
+    assert!(set.contains(Diet::Plants));
+    assert!(!set.contains(Diet::Fish));
+    assert_eq!(set.bits(), 0b110);
+    assert_eq!(
+        set.diets().collect::<Vec<_>>(),
+        vec![Diet::Shellfish, Diet::Plants]
+    );
+
+    assert!(DietSet::empty().is_empty());
+    assert_eq!(DietSet::omnivore().bits(), 0b111);
+    assert_eq!(DietSet::from(Diet::Fish), DietSet::only(Diet::Fish));
+
+    let weighted = DietSet::only(Diet::Fish).with(Diet::Plants, 3);
+    assert_eq!(weighted.weight(Diet::Plants), 3);
+    assert_eq!(weighted.weight(Diet::Shellfish), 0);
+    assert_eq!((weighted | Diet::Plants).weight(Diet::Plants), 3);
+    assert_eq!(weighted.intersection(&set), DietSet::only(Diet::Plants));
+    assert!(!weighted.with(Diet::Fish, 0).contains(Diet::Fish));
+}
+
+#[test]
+fn diet_omnivore_hunts_anything() {
+    let mut crab = new_crab(Diet::Shellfish).with_diets(DietSet::omnivore());
+    let reef = share(Reef::new());
+    write(&reef).add_prey(Box::new(Algae::new()));
+    write(&reef).add_prey(Box::new(Clam::new()));
+    crab.discover_reef(SharedReef::clone(&reef));
+
+    let first = crab.hunt_detailed();
+    assert_eq!(first.caught(), Some(&PreyKind::Algae));
+    assert!(first.inedible().is_empty());
+    let second = crab.hunt_detailed();
+    assert_eq!(second.caught(), Some(&PreyKind::Clam));
+}
+
+#[test]
+fn diet_single_diet_by_default() {
+    let mut crab = new_crab(Diet::Shellfish);
+    assert_eq!(crab.diets(), &DietSet::only(Diet::Shellfish));
+
+    let reef = share(Reef::new());
+    write(&reef).add_prey(Box::new(Algae::new()));
+    crab.discover_reef(SharedReef::clone(&reef));
+    assert!(!crab.hunt());
+
+    crab.set_diets(Diet::Shellfish | Diet::Plants);
+    assert!(crab.hunt());
+}
+
+#[test]
+fn diet_choose_recipe_by_weight() {
+    let cookbook = Cookbook::new();
+
+    // Equal weights: the first matching recipe in the cookbook (chowder).
+    let crab = new_crab(Diet::Fish).with_diets(DietSet::omnivore());
+    assert_eq!(crab.choose_recipe(&cookbook).unwrap().name(), "chowder");
+
+    // A strong preference for fish wins.
+    let crab = crab.with_diets(DietSet::omnivore().with(Diet::Fish, 5));
+    assert_eq!(crab.choose_recipe(&cookbook).unwrap().name(), "cioppino");
+
+    let crab = crab.with_diets(DietSet::only(Diet::Plants));
+    assert!(crab.choose_recipe(&cookbook).is_none());
+}
+
+#[test]
+fn diet_breed_inherits_shared_diets() {
+    let mut rng = OceanRng::new(0);
+    let rates = MutationRates::none();
+    let c1 = new_crab(Diet::Fish).with_diets(Diet::Fish | Diet::Plants);
+    let c2 = new_crab(Diet::Fish).with_diets(DietSet::omnivore());
+
+    let baby = Crab::breed(&c1, &c2, String::from("Baby"), &rates, &mut rng);
+    assert_eq!(baby.diet(), Diet::Fish);
+    assert_eq!(baby.diets(), &(Diet::Fish | Diet::Plants));
+
+    // Single-diet parents have single-diet babies.
+    let c3 = new_crab(Diet::Fish);
+    let baby = Crab::breed(&c1, &c3, String::from("Baby"), &rates, &mut rng);
+    assert_eq!(baby.diets(), &DietSet::only(Diet::Fish));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn diet_set_json() {
+    let set = DietSet::only(Diet::Shellfish).with(Diet::Plants, 2);
+    let json = serde_json::to_string(&set).unwrap();
+    assert_eq!(json, r#"{"Shellfish":1,"Plants":2}"#);
+    assert_eq!(serde_json::from_str::<DietSet>(&json).unwrap(), set);
+}
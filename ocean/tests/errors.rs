@@ -0,0 +1,106 @@
+extern crate ocean;
+
+/*
+ * Run `cargo test error` to run all of the error handling tests.
+ */
+
+use ocean::beach::*;
+use ocean::color::*;
+use ocean::crab::*;
+use ocean::diet::*;
+use ocean::error::*;
+
+/* Fixtures */
+
+fn new_crab(name: &str) -> Crab {
+    Crab::new(String::from(name), 10, Color::new_green(), Diet::Plants)
+}
+
+fn new_couple_beach() -> Beach {
+    let mut beach = Beach::new();
+    beach.add_crab(new_crab("Ann"));
+    beach.add_crab(new_crab("Bob"));
+    beach
+}
+
+/* Tests */
+
+#[test]
+fn error_beach_try_get_crab() {
+    let beach = new_couple_beach();
+
+    assert_eq!(beach.try_get_crab(1).unwrap().name(), "Bob");
+    assert_eq!(
+        beach.try_get_crab(2).unwrap_err(),
+        OceanError::UnknownCrab { index: 2, size: 2 }
+    );
+}
+
+#[test]
+fn error_beach_remove_crab() {
+    let mut beach = new_couple_beach();
+
+    let ann = beach.remove_crab(0).unwrap();
+    assert_eq!(ann.name(), "Ann");
+    assert_eq!(beach.size(), 1);
+    assert_eq!(beach.get_crab(0).name(), "Bob");
+
+    assert_eq!(
+        beach.remove_crab(1).unwrap_err(),
+        OceanError::UnknownCrab { index: 1, size: 1 }
+    );
+}
+
+#[test]
+fn error_beach_try_breed_crabs() {
+    let mut beach = new_couple_beach();
+
+    assert_eq!(
+        beach
+            .try_breed_crabs(0, 5, String::from("Kid"))
+            .unwrap_err(),
+        OceanError::UnknownCrab { index: 5, size: 2 }
+    );
+    assert_eq!(
+        beach
+            .try_breed_crabs(1, 1, String::from("Kid"))
+            .unwrap_err(),
+        OceanError::SelfBreeding { index: 1 }
+    );
+    assert_eq!(beach.size(), 2);
+
+    assert!(beach.try_breed_crabs(0, 1, String::from("Kid")).is_ok());
+    assert_eq!(beach.size(), 3);
+}
+
+#[test]
+fn error_beach_capacity_exceeded() {
+    let mut beach = Beach::with_capacity(3);
+    beach.add_crab(new_crab("Ann"));
+    beach.add_crab(new_crab("Bob"));
+    assert!(!beach.is_full());
+
+    assert!(beach.try_breed_crabs(0, 1, String::from("Kid")).is_ok());
+    assert!(beach.is_full());
+    assert_eq!(
+        beach
+            .try_breed_crabs(0, 1, String::from("Kid 2"))
+            .unwrap_err(),
+        OceanError::CapacityExceeded { capacity: 3 }
+    );
+}
+
+#[test]
+#[should_panic(expected = "cannot breed with itself")]
+fn error_beach_breed_crabs_self_panics() {
+    let mut beach = new_couple_beach();
+    beach.breed_crabs(0, 0, String::from("Kid"));
+}
+
+#[test]
+#[should_panic(expected = "capacity of 1 crabs")]
+fn error_beach_add_crab_full_panics() {
+    let mut beach = Beach::with_capacity(1);
+    beach.add_crab(new_crab("Ann"));
+    beach.add_crab(new_crab("Bob"));
+}
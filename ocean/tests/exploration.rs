@@ -0,0 +1,199 @@
+extern crate ocean;
+
+/*
+ * Run `cargo test explore_` to run all of the exploration tests.
+ */
+
+use ocean::beach::*;
+use ocean::color::*;
+use ocean::crab::*;
+use ocean::diet::*;
+use ocean::exploration::*;
+use ocean::grid::*;
+use ocean::ocean::*;
+use ocean::prey::*;
+use ocean::reef::*;
+use ocean::shared::*;
+use ocean::strategy::*;
+
+/* Fixtures */
+
+/**
+ * A slow shellfish-eating crab (range 4).
+ */
+fn new_slow_crab(name: &str) -> Crab {
+    Crab::new(String::from(name), 2, Color::new_red(), Diet::Shellfish)
+}
+
+/**
+ * An ocean whose crabs explore at the given rates, with a reef of clams at each
+ * of the given positions.
+ */
+fn new_ocean(rates: ExplorationRates, positions: &[Position]) -> Ocean {
+    let mut ocean = Ocean::new();
+    ocean.set_exploration(Some(rates));
+    for &position in positions {
+        let spec = ReefSpec::new().clams(5).position(position);
+        ocean.generate_reef_from(&spec).unwrap();
+    }
+    ocean
+}
+
+fn crab(ocean: &Ocean, index: usize) -> &Crab {
+    ocean.beaches().next().unwrap().get_crab(index)
+}
+
+/* Tests */
+
+#[test]
+fn explore_discovers_reefs_in_range() {
+    let rates = ExplorationRates {
+        discovery: 1.0,
+        ..ExplorationRates::none()
+    };
+    let mut ocean = new_ocean(rates, &[Position::new(0, 3), Position::new(50, 50)]);
+    let mut beach = Beach::new();
+    beach.add_crab(new_slow_crab("Shelly"));
+    ocean.add_beach(beach);
+
+    let report = ocean.step();
+    assert_eq!(report.reefs_discovered(), 1);
+    assert_eq!(report.fed(), 1);
+    let reefs: Vec<Position> = crab(&ocean, 0)
+        .reefs()
+        .map(|r| read(r).position())
+        .collect();
+    assert_eq!(reefs, vec![Position::new(0, 3)]);
+
+    // Known reefs are not discovered again.
+    assert_eq!(ocean.step().reefs_discovered(), 0);
+}
+
+#[test]
+fn explore_without_rates_discovers_nothing() {
+    let mut ocean = new_ocean(ExplorationRates::default(), &[Position::default()]);
+    ocean.set_exploration(None);
+    let mut beach = Beach::new();
+    beach.add_crab(new_slow_crab("Shelly"));
+    ocean.add_beach(beach);
+
+    for report in ocean.run(20) {
+        assert_eq!(report.reefs_discovered(), 0);
+        assert_eq!(report.fed(), 0);
+    }
+}
+
+#[test]
+fn explore_shares_stocked_reefs_within_beach() {
+    let rates = ExplorationRates {
+        sharing: 1.0,
+        ..ExplorationRates::none()
+    };
+    let mut ocean = new_ocean(rates, &[Position::default()]);
+    let stocked = SharedReef::clone(ocean.reefs().next().unwrap());
+    let empty = share(Reef::new());
+
+    let mut scout = new_slow_crab("Scout");
+    scout.discover_reef(SharedReef::clone(&empty));
+    scout.discover_reef(SharedReef::clone(&stocked));
+    let mut beach = Beach::new();
+    beach.add_crab(scout);
+    beach.add_crab(new_slow_crab("Follower"));
+    ocean.add_beach(beach);
+    let mut elsewhere = Beach::new();
+    elsewhere.add_crab(new_slow_crab("Stranger"));
+    ocean.add_beach(elsewhere);
+
+    assert_eq!(ocean.step().reefs_discovered(), 1);
+    let follower = crab(&ocean, 1);
+    assert!(follower.knows_reef(&stocked));
+    assert!(!follower.knows_reef(&empty));
+    let stranger = ocean.beaches().nth(1).unwrap().get_crab(0);
+    assert!(!stranger.knows_reef(&stocked));
+}
+
+#[test]
+fn explore_does_not_rediscover_forgotten_reef() {
+    let rates = ExplorationRates {
+        discovery: 1.0,
+        forget_after: 1,
+        ..ExplorationRates::none()
+    };
+    let mut ocean = new_ocean(rates, &[Position::new(0, 3)]);
+    let reef = SharedReef::clone(ocean.reefs().next().unwrap());
+    while write(&reef).take_prey().is_some() {}
+
+    let mut shelly = new_slow_crab("Shelly");
+    shelly.discover_reef(SharedReef::clone(&reef));
+    let mut beach = Beach::new();
+    beach.add_crab(shelly);
+    ocean.add_beach(beach);
+
+    let report = ocean.step();
+    assert_eq!(report.reefs_forgotten(), 1);
+    assert_eq!(report.reefs_discovered(), 0);
+    assert!(!crab(&ocean, 0).knows_reef(&reef));
+}
+
+#[test]
+fn explore_forgets_empty_reefs() {
+    let empty = share(Reef::new());
+    let stocked = share(Reef::new());
+    write(&stocked).add_prey(Box::new(Clam::new()));
+    let mut crab = new_slow_crab("Shelly");
+    crab.discover_reef(SharedReef::clone(&empty));
+    crab.discover_reef(SharedReef::clone(&stocked));
+
+    assert_eq!(crab.forget_empty_reefs(2), 0);
+    assert_eq!(crab.reef_empty_ticks(0), Some(1));
+    assert_eq!(crab.reef_empty_ticks(1), Some(0));
+
+    // Prey coming back resets the count.
+    write(&empty).add_prey(Box::new(Clam::new()));
+    assert_eq!(crab.forget_empty_reefs(2), 0);
+    assert_eq!(crab.reef_empty_ticks(0), Some(0));
+
+    write(&empty).take_prey();
+    assert_eq!(crab.forget_empty_reefs(2), 0);
+    assert_eq!(crab.forget_empty_reefs(2), 1);
+    assert_eq!(crab.reefs().len(), 1);
+    assert!(crab.knows_reef(&stocked));
+    assert!(!crab.knows_reef(&empty));
+    assert_eq!(crab.reef_empty_ticks(1), None);
+
+    // Crabs with a forget_after of zero never forget.
+    let mut crab = new_slow_crab("Elephant");
+    crab.discover_reef(share(Reef::new()));
+    for _ in 0..10 {
+        assert_eq!(crab.forget_empty_reefs(0), 0);
+    }
+    assert_eq!(crab.reef_empty_ticks(0), Some(10));
+}
+
+#[test]
+fn explore_forget_reef_keeps_strategy_in_step() {
+    let reefs: Vec<SharedReef> = (0..3)
+        .map(|_| {
+            let reef = share(Reef::new());
+            write(&reef).add_prey(Box::new(Clam::new()));
+            reef
+        })
+        .collect();
+    let last = StrategyRecord::new("remember_success").with("last", 2);
+    let mut crab = new_slow_crab("Shelly").with_strategy(from_record(&last).unwrap());
+    for reef in &reefs {
+        crab.discover_reef(SharedReef::clone(reef));
+    }
+
+    assert!(crab.forget_reef(0).is_some());
+    assert_eq!(
+        crab.strategy().to_record(),
+        StrategyRecord::new("remember_success").with("last", 1)
+    );
+    assert!(crab.forget_reef(1).is_some());
+    assert_eq!(
+        crab.strategy().to_record(),
+        StrategyRecord::new("remember_success")
+    );
+    assert!(crab.forget_reef(5).is_none());
+}
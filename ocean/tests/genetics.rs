@@ -0,0 +1,73 @@
+extern crate ocean;
+
+/*
+ * Run `cargo test genome` to run all of the genetics tests.
+ */
+
+use ocean::beach::*;
+use ocean::color::*;
+use ocean::crab::*;
+use ocean::diet::*;
+use ocean::genome::*;
+use ocean::rand::*;
+
+/* Fixtures */
+
+fn new_fast_fish_eater() -> Crab {
+    Crab::new(String::from("Dash"), 40, Color::new_red(), Diet::Fish)
+}
+
+fn new_slow_fish_eater() -> Crab {
+    Crab::new(String::from("Plod"), 10, Color::new_green(), Diet::Fish)
+}
+
+/* Tests */
+
+#[test]
+fn genome_crossover_inherits_from_parents() {
+    let mut rng = OceanRng::new(0);
+    let g1 = Genome::new(40, Color::new_red(), Diet::Fish);
+    let g2 = Genome::new(10, Color::new_green(), Diet::Shellfish);
+
+    for _ in 0..100 {
+        let child = Genome::crossover(&g1, &g2, &MutationRates::none(), &mut rng);
+        assert!(child.speed() == 40 || child.speed() == 10);
+        assert!(child.diet() == Diet::Fish || child.diet() == Diet::Shellfish);
+        assert_eq!(child.color(), &Color::new(255, 255, 0));
+    }
+}
+
+#[test]
+fn genome_crossover_speed_mutation() {
+    let mut rng = OceanRng::new(0);
+    let g = Genome::new(20, Color::new_red(), Diet::Fish);
+    let rates = MutationRates {
+        speed: 1.0,
+        ..MutationRates::none()
+    };
+
+    for _ in 0..100 {
+        let child = Genome::crossover(&g, &g, &rates, &mut rng);
+        assert!(child.speed() == 19 || child.speed() == 21);
+    }
+}
+
+#[test]
+fn genome_beach_offspring_grow_to_inherited_speed() {
+    let mut beach = Beach::new();
+    beach.set_mutation_rates(MutationRates::none());
+    beach.add_crab(new_fast_fish_eater());
+    beach.add_crab(new_fast_fish_eater());
+    beach.add_crab(new_slow_fish_eater());
+
+    beach.breed_crabs(0, 1, String::from("Zoom"));
+    let baby = beach.get_crab(3);
+    assert_eq!(baby.speed(), 1);
+    assert_eq!(baby.genome().speed(), 40);
+
+    let baby = beach.crabs_mut().nth(3).unwrap();
+    for _ in 0..100 {
+        baby.grow();
+    }
+    assert_eq!(baby.speed(), 40);
+}
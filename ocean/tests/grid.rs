@@ -0,0 +1,121 @@
+extern crate ocean;
+
+/*
+ * Run `cargo test grid_` to run all of the ocean grid tests.
+ */
+
+use ocean::beach::*;
+use ocean::color::*;
+use ocean::crab::*;
+use ocean::diet::*;
+use ocean::error::*;
+use ocean::grid::*;
+use ocean::ocean::*;
+use ocean::prey::*;
+use ocean::reef::*;
+use ocean::shared::*;
+
+/* Fixtures */
+
+/**
+ * A slow shellfish-eating crab (range 4), living at the origin.
+ */
+fn new_slow_crab() -> Crab {
+    Crab::new(String::from("Shelly"), 2, Color::new_red(), Diet::Shellfish)
+}
+
+fn new_clam_reef(position: Position) -> SharedReef {
+    let mut reef = Reef::new();
+    reef.add_prey(Box::new(Clam::new()));
+    reef.set_position(position);
+    share(reef)
+}
+
+/* Tests */
+
+#[test]
+fn grid_distance_and_travel_time() {
+    let origin = Position::default();
+    let far = Position::new(3, -4);
+
+    assert_eq!(origin.distance(&far), 5.0);
+    assert_eq!(far.distance(&origin), 5.0);
+    assert_eq!(origin.travel_time(&far, 5), 1);
+    assert_eq!(origin.travel_time(&far, 2), 3);
+    assert_eq!(origin.travel_time(&far, 0), u32::MAX);
+    assert_eq!(far.travel_time(&far, 0), 0);
+    assert_eq!(far.to_string(), "(3, -4)");
+}
+
+#[test]
+fn grid_beach_is_home() {
+    let mut beach = Beach::new();
+    beach.set_position(Position::new(1, 2));
+    beach.add_crab(new_slow_crab());
+    assert_eq!(beach.get_crab(0).home(), Position::new(1, 2));
+
+    // Moving the beach moves everyone's home with it.
+    beach.set_position(Position::new(5, 5));
+    assert_eq!(beach.get_crab(0).home(), Position::new(5, 5));
+}
+
+#[test]
+fn grid_discovery_limited_by_range() {
+    let mut crab = new_slow_crab();
+    assert_eq!(crab.range(), 4);
+
+    let near = new_clam_reef(Position::new(0, 4));
+    let far = new_clam_reef(Position::new(3, 4));
+    assert!(crab.can_reach(&read(&near)));
+    assert!(!crab.can_reach(&read(&far)));
+
+    assert_eq!(crab.try_discover_reef(near), Ok(()));
+    assert_eq!(
+        crab.try_discover_reef(far),
+        Err(OceanError::OutOfRange {
+            distance: 5,
+            range: 4
+        })
+    );
+    assert!(crab.hunt());
+    assert!(!crab.hunt());
+}
+
+#[test]
+fn grid_reefs_within() {
+    let mut ocean = Ocean::new();
+    for x in [0, 3, 6] {
+        let spec = ReefSpec::new().clams(1).position(Position::new(x, 0));
+        ocean.generate_reef_from(&spec).unwrap();
+    }
+
+    let positions: Vec<Position> = ocean
+        .reefs_within(Position::new(1, 0), 2)
+        .map(|r| read(r).position())
+        .collect();
+    assert_eq!(positions, vec![Position::new(0, 0), Position::new(3, 0)]);
+    assert_eq!(ocean.reefs_within(Position::new(20, 20), 4).count(), 0);
+}
+
+#[test]
+fn grid_hunt_costs_travel() {
+    let mut crab = new_slow_crab();
+    crab.discover_reef(new_clam_reef(Position::new(0, 4)));
+
+    let report = crab.hunt_detailed();
+    assert_eq!(report.visited(), &[0]);
+    assert_eq!(report.travel_time(), 2);
+    assert_eq!(
+        crab.energy(),
+        Crab::INITIAL_ENERGY - 2 * Crab::METABOLISM + Clam::new().nutrition()
+    );
+
+    // Reefs right at home are free to reach.
+    let mut crab = new_slow_crab();
+    crab.discover_reef(new_clam_reef(Position::default()));
+    assert_eq!(crab.hunt_detailed().travel_time(), 0);
+    assert_eq!(
+        crab.energy(),
+        Crab::INITIAL_ENERGY + Clam::new().nutrition()
+    );
+}
@@ -0,0 +1,83 @@
+extern crate ocean;
+
+/*
+ * Run `cargo test hunt_` to run all of the hunt report tests.
+ */
+
+use ocean::color::*;
+use ocean::crab::*;
+use ocean::diet::*;
+use ocean::prey::*;
+use ocean::reef::*;
+use ocean::shared::*;
+
+/* Fixtures */
+
+/**
+ * A shellfish-eating crab that knows about a reef of algae, and a reef with a
+ * tired shrimp and a minnow too fast for it.
+ */
+fn new_hunter() -> (Crab, Vec<SharedReef>) {
+    let mut crab = Crab::new(
+        String::from("Clawdia"),
+        20,
+        Color::new_blue(),
+        Diet::Shellfish,
+    );
+    let algae = share(Reef::new());
+    write(&algae).add_prey(Box::new(Algae::new()));
+    let mixed = share(Reef::new());
+    write(&mixed).add_prey(Box::new(Shrimp::new(1)));
+    write(&mixed).add_prey(Box::new(Minnow::new(30)));
+
+    crab.discover_reef(SharedReef::clone(&algae));
+    crab.discover_reef(SharedReef::clone(&mixed));
+    (crab, vec![algae, mixed])
+}
+
+/* Tests */
+
+#[test]
+fn hunt_report_failure() {
+    let (mut crab, reefs) = new_hunter();
+
+    let report = crab.hunt_detailed();
+    assert!(!report.is_success());
+    assert_eq!(report.caught(), None);
+    assert_eq!(report.reef(), None);
+    assert_eq!(report.attempts(), 3);
+    assert_eq!(report.escaped(), &[PreyKind::Shrimp, PreyKind::Minnow]);
+    assert_eq!(report.inedible(), &[PreyKind::Algae]);
+    assert!(crab.is_hungry());
+
+    // Everything was released back where it came from.
+    assert_eq!(read(&reefs[0]).population(), 1);
+    assert_eq!(read(&reefs[1]).population(), 2);
+}
+
+#[test]
+fn hunt_report_success() {
+    let (mut crab, reefs) = new_hunter();
+    crab.hunt_detailed();
+
+    // The shrimp is too tired to escape a second time.
+    let report = crab.hunt_detailed();
+    assert!(report.is_success());
+    assert_eq!(report.caught(), Some(&PreyKind::Shrimp));
+    assert_eq!(report.reef(), Some(1));
+    assert_eq!(report.attempts(), 2);
+    assert!(report.escaped().is_empty());
+    assert_eq!(report.inedible(), &[PreyKind::Algae]);
+    assert!(!crab.is_hungry());
+    assert_eq!(read(&reefs[1]).population(), 1);
+}
+
+#[test]
+fn hunt_report_matches_hunt() {
+    let (mut a, _reefs_a) = new_hunter();
+    let (mut b, _reefs_b) = new_hunter();
+    for _ in 0..3 {
+        assert_eq!(a.hunt(), b.hunt_detailed().is_success());
+        assert_eq!(a.energy(), b.energy());
+    }
+}
@@ -0,0 +1,182 @@
+extern crate ocean;
+
+/*
+ * Run `cargo test migrate_` to run all of the migration tests.
+ */
+
+use ocean::beach::*;
+use ocean::color::*;
+use ocean::crab::*;
+use ocean::diet::*;
+use ocean::error::*;
+use ocean::grid::*;
+use ocean::migration::*;
+use ocean::ocean::*;
+use ocean::shared::*;
+
+/* Fixtures */
+
+fn new_crab(name: &str) -> Crab {
+    Crab::new(String::from(name), 20, Color::new_red(), Diet::Shellfish)
+}
+
+fn new_beach(ocean: &Ocean, x: i32, capacity: Option<usize>) -> Beach {
+    let mut beach = ocean.new_beach();
+    beach.set_position(Position::new(x, 0));
+    beach.set_capacity(capacity);
+    beach
+}
+
+fn sizes(ocean: &Ocean) -> Vec<usize> {
+    ocean.beaches().map(|b| b.size()).collect()
+}
+
+/* Tests */
+
+#[test]
+fn migrate_crab_keeps_reefs_and_ancestry() {
+    let mut ocean = Ocean::new();
+    let reef = ocean.generate_reef(0, 0, 3, 0);
+    let mut beach = new_beach(&ocean, 0, None);
+    beach.add_crab(new_crab("Mom"));
+    beach.add_crab(new_crab("Dad"));
+    beach.breed_crabs(0, 1, String::from("Junior"));
+    let mut crab = beach.remove_crab(2).unwrap();
+    let (junior, (mom, dad)) = (crab.id(), crab.parents().unwrap());
+    crab.discover_reef(SharedReef::clone(&reef));
+    beach.add_crab(crab);
+    ocean.add_beach(beach);
+    ocean.add_beach(new_beach(&ocean, 10, None));
+
+    assert_eq!(ocean.migrate_crab(0, junior, 1), Ok(()));
+    assert_eq!(sizes(&ocean), vec![2, 1]);
+    let there = ocean.beaches().nth(1).unwrap();
+    let crab = there.find_crab(junior).unwrap();
+    assert_eq!(crab.home(), Position::new(10, 0));
+    assert!(crab.knows_reef(&reef));
+    assert_eq!(there.pedigree().parents(junior), Some((mom, dad)));
+    assert!(there.pedigree().contains(mom));
+    assert!(there.pedigree().contains(dad));
+}
+
+#[test]
+fn migrate_crab_errors() {
+    let mut ocean = Ocean::new();
+    let mut beach = new_beach(&ocean, 0, None);
+    beach.add_crab(new_crab("Pinchy"));
+    let id = beach.get_crab(0).id();
+    ocean.add_beach(beach);
+    let mut full = new_beach(&ocean, 5, Some(1));
+    full.add_crab(new_crab("Sebastian"));
+    let other = full.get_crab(0).id();
+    ocean.add_beach(full);
+
+    assert_eq!(
+        ocean.migrate_crab(0, id, 2),
+        Err(OceanError::UnknownBeach { index: 2, count: 2 })
+    );
+    assert_eq!(
+        ocean.migrate_crab(0, other, 1),
+        Err(OceanError::MissingCrab { id: other })
+    );
+    assert_eq!(
+        ocean.migrate_crab(0, id, 1),
+        Err(OceanError::CapacityExceeded { capacity: 1 })
+    );
+    assert_eq!(ocean.migrate_crab(0, id, 0), Ok(()));
+    assert_eq!(sizes(&ocean), vec![1, 1]);
+}
+
+#[test]
+fn migrate_crab_ids_stable_and_unique() {
+    let mut ocean = Ocean::new();
+    let mut here = Beach::new();
+    here.add_crab(new_crab("Pinchy"));
+    let pinchy = here.get_crab(0).id();
+    ocean.add_beach(here);
+
+    // A beach made on its own hands out ids from zero too...
+    let mut there = Beach::new();
+    there.set_position(Position::new(5, 0));
+    there.add_crab(new_crab("Sebastian"));
+    let sebastian = there.get_crab(0).id();
+    assert_eq!(pinchy, sebastian);
+
+    // ... and its crabs keep them when it joins the ocean.
+    ocean.add_beach(there);
+    assert_eq!(ocean.beaches().nth(1).unwrap().get_crab(0).id(), sebastian);
+
+    // A crab cannot move onto a beach where a different crab has its id.
+    assert_eq!(
+        ocean.migrate_crab(0, pinchy, 1),
+        Err(OceanError::DuplicateCrabId { id: pinchy })
+    );
+    assert_eq!(sizes(&ocean), vec![1, 1]);
+
+    // Crabs added from now on get ids from the ocean, clear of both.
+    let beach = ocean.beaches_mut().nth(1).unwrap();
+    beach.add_crab(new_crab("Clawdia"));
+    assert!(beach.get_crab(1).id() > sebastian);
+}
+
+#[test]
+fn migrate_away_from_overcrowding() {
+    let mut ocean = Ocean::new();
+    ocean.set_migration(Some(MigrationPolicy {
+        crowded_at: 0.5,
+        crowding: 1.0,
+        scarcity: 0.0,
+    }));
+    let mut crowded = new_beach(&ocean, 0, Some(2));
+    crowded.add_crab(new_crab("Pinchy"));
+    crowded.add_crab(new_crab("Clawdia"));
+    ocean.add_beach(crowded);
+    ocean.add_beach(new_beach(&ocean, 5, Some(10)));
+
+    // Nobody leaves a beach without a capacity, or a roomy one.
+    let report = ocean.step();
+    assert_eq!(report.migrated(), 2);
+    assert_eq!(sizes(&ocean), vec![0, 2]);
+    assert_eq!(ocean.step().migrated(), 0);
+    assert_eq!(sizes(&ocean), vec![0, 2]);
+}
+
+#[test]
+fn migrate_hungry_crabs_to_nearest_beach() {
+    let mut ocean = Ocean::new();
+    ocean.set_migration(Some(MigrationPolicy {
+        scarcity: 1.0,
+        ..MigrationPolicy::none()
+    }));
+    let reef = ocean.generate_reef(0, 0, 5, 0);
+    let mut beach = new_beach(&ocean, 0, None);
+    let mut fed = new_crab("Fed");
+    fed.discover_reef(SharedReef::clone(&reef));
+    beach.add_crab(fed);
+    beach.add_crab(new_crab("Hungry"));
+    ocean.add_beach(beach);
+    ocean.add_beach(new_beach(&ocean, 8, None));
+    let mut full = new_beach(&ocean, 1, Some(1));
+    let mut local = new_crab("Local");
+    local.discover_reef(reef);
+    full.add_crab(local);
+    ocean.add_beach(full);
+    ocean.add_beach(new_beach(&ocean, -3, None));
+
+    assert_eq!(ocean.step().migrated(), 1);
+    assert_eq!(sizes(&ocean), vec![1, 0, 1, 1]);
+    assert_eq!(ocean.beaches().nth(3).unwrap().get_crab(0).name(), "Hungry");
+}
+
+#[test]
+fn migrate_never_without_policy() {
+    let mut ocean = Ocean::new();
+    let mut crowded = new_beach(&ocean, 0, Some(1));
+    crowded.add_crab(new_crab("Homebody"));
+    ocean.add_beach(crowded);
+    ocean.add_beach(new_beach(&ocean, 1, None));
+
+    assert!(ocean.migration().is_none());
+    assert_eq!(ocean.step().migrated(), 0);
+    assert_eq!(sizes(&ocean), vec![1, 0]);
+}
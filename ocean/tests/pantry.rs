@@ -0,0 +1,195 @@
+extern crate ocean;
+
+/*
+ * Run `cargo test pantry_` to run all of the pantry tests.
+ */
+
+use ocean::beach::*;
+use ocean::color::*;
+use ocean::crab::*;
+use ocean::diet::*;
+use ocean::ocean::*;
+use ocean::pantry::*;
+use ocean::prey::*;
+use ocean::reef::*;
+use ocean::shared::*;
+
+/* Fixtures */
+
+fn new_clam_eater() -> Crab {
+    Crab::new(
+        String::from("Prinz"),
+        20,
+        Color::new_blue(),
+        Diet::Shellfish,
+    )
+}
+
+fn new_clam_reef(clams: usize) -> SharedReef {
+    let mut reef = Reef::new();
+    for _ in 0..clams {
+        reef.add_prey(Box::new(Clam::new()));
+    }
+    share(reef)
+}
+
+fn fill_up(crab: &mut Crab) {
+    while crab.energy() < Crab::MAX_ENERGY {
+        crab.eat(Box::new(Clam::new()));
+    }
+}
+
+/* Tests */
+
+#[test]
+fn pantry_capacity() {
+    let mut pantry = Pantry::with_capacity(2);
+    assert!(pantry.add_prey(Box::new(Clam::new())).is_none());
+    assert!(pantry.add_prey(Box::new(Algae::new())).is_none());
+    assert!(pantry.is_full());
+
+    let rejected = pantry.add_prey(Box::new(Minnow::new(3)));
+    assert_eq!(rejected.unwrap().kind(), PreyKind::Minnow);
+    assert_eq!(pantry.size(), 2);
+
+    pantry.set_capacity(3);
+    assert!(!pantry.is_full());
+}
+
+#[test]
+fn pantry_queries() {
+    let mut pantry = Pantry::new();
+    pantry.add_prey(Box::new(Shrimp::new(2)));
+    pantry.add_prey(Box::new(Clam::new()));
+    pantry.add_prey(Box::new(Algae::new()));
+    pantry.add_prey(Box::new(Shrimp::new(0)));
+
+    assert_eq!(pantry.count(&PreyKind::Shrimp), 2);
+    assert_eq!(pantry.count(&PreyKind::Minnow), 0);
+    assert_eq!(pantry.count_diet(Diet::Shellfish), 3);
+    assert_eq!(pantry.count_diet(Diet::Plants), 1);
+
+    // Oldest first.
+    let shrimp = pantry.take(&PreyKind::Shrimp).unwrap();
+    assert_eq!(shrimp.to_record().get("energy"), Some(2));
+    assert_eq!(
+        pantry.take_diet(Diet::Shellfish).unwrap().kind(),
+        PreyKind::Clam
+    );
+    assert!(pantry.take_diet(Diet::Fish).is_none());
+    assert_eq!(pantry.size(), 2);
+}
+
+#[test]
+fn pantry_spoils() {
+    let mut pantry = Pantry::new();
+    pantry.set_shelf_life(2);
+    pantry.add_prey(Box::new(Clam::new()));
+    assert_eq!(pantry.age(), 0);
+    pantry.add_prey(Box::new(Algae::new()));
+
+    assert_eq!(pantry.age(), 1);
+    assert_eq!(
+        pantry
+            .prey_with_ages()
+            .map(|(p, age)| (p.kind(), age))
+            .collect::<Vec<_>>(),
+        vec![(PreyKind::Algae, 1)]
+    );
+    assert_eq!(pantry.age(), 1);
+    assert!(pantry.is_empty());
+}
+
+#[test]
+fn pantry_hunt_stores_when_full() {
+    let mut crab = new_clam_eater();
+    let reef = new_clam_reef(2);
+    crab.discover_reef(SharedReef::clone(&reef));
+
+    // A crab with room for more energy eats what it catches...
+    crab.metabolize();
+    let report = crab.hunt_detailed();
+    assert!(report.is_success());
+    assert!(!report.is_stored());
+    assert!(crab.pantry().is_empty());
+
+    // ...but a full crab saves it for later.
+    fill_up(&mut crab);
+    let report = crab.hunt_detailed();
+    assert!(report.is_success());
+    assert!(report.is_stored());
+    assert_eq!(crab.pantry().count(&PreyKind::Clam), 1);
+    assert_eq!(read(&reef).population(), 0);
+}
+
+#[test]
+fn pantry_hunt_eats_when_pantry_full() {
+    let mut crab = new_clam_eater();
+    crab.pantry_mut().set_capacity(0);
+    crab.discover_reef(new_clam_reef(1));
+    fill_up(&mut crab);
+
+    let report = crab.hunt_detailed();
+    assert!(report.is_success());
+    assert!(!report.is_stored());
+    assert_eq!(crab.energy(), Crab::MAX_ENERGY);
+}
+
+#[test]
+fn pantry_eat_from_pantry() {
+    let mut crab = new_clam_eater();
+    crab.pantry_mut().add_prey(Box::new(Algae::new()));
+    assert!(!crab.eat_from_pantry());
+
+    crab.pantry_mut().add_prey(Box::new(Clam::new()));
+    crab.metabolize();
+    assert!(crab.eat_from_pantry());
+    assert_eq!(crab.energy(), Crab::INITIAL_ENERGY - Crab::METABOLISM + 4);
+    assert_eq!(crab.pantry().count(&PreyKind::Algae), 1);
+}
+
+#[test]
+fn pantry_ocean_step_eats_and_spoils() {
+    let mut ocean = Ocean::new();
+    let mut crab = new_clam_eater();
+    crab.pantry_mut().set_shelf_life(3);
+    crab.pantry_mut().add_prey(Box::new(Clam::new()));
+    crab.pantry_mut().add_prey(Box::new(Algae::new()));
+    let mut beach = Beach::new();
+    beach.add_crab(crab);
+    ocean.add_beach(beach);
+
+    // With no reefs to hunt in, the crab eats the clam from its pantry.
+    ocean.step();
+    let crab = ocean.beaches().next().unwrap().get_crab(0);
+    assert_eq!(crab.energy(), Crab::INITIAL_ENERGY + 4 - Crab::METABOLISM);
+    assert_eq!(crab.pantry().size(), 1);
+
+    // The inedible algae spoils after three ticks.
+    ocean.run(2);
+    assert!(ocean
+        .beaches()
+        .next()
+        .unwrap()
+        .get_crab(0)
+        .pantry()
+        .is_empty());
+}
+
+#[test]
+fn pantry_throws_out_spoiled_prey() {
+    let mut pantry = Pantry::new();
+    pantry.set_shelf_life(100);
+    pantry.add_prey(Box::new(Minnow::new(25)));
+    pantry.add_prey(Box::new(Clam::new()));
+
+    for _ in 0..9 {
+        assert_eq!(pantry.age(), 0);
+    }
+    assert_eq!(pantry.prey().next().unwrap().freshness(), 10);
+
+    // The minnow spoils on its tenth tick, long before the clam.
+    assert_eq!(pantry.age(), 1);
+    assert_eq!(pantry.count(&PreyKind::Clam), 1);
+    assert_eq!(pantry.prey().next().unwrap().freshness(), 60);
+}
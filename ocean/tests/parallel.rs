@@ -0,0 +1,81 @@
+#![cfg(feature = "sync")]
+
+extern crate ocean;
+
+/*
+ * Run `cargo test --features sync parallel` to run all of the thread-safety tests.
+ */
+
+use ocean::beach::*;
+use ocean::color::*;
+use ocean::crab::*;
+use ocean::diet::*;
+use ocean::ocean::*;
+use ocean::shared::SharedReef;
+
+/* Fixtures */
+
+/**
+ * An ocean of eight beaches. Pairs of neighboring beaches share a reef, and every
+ * beach also has a reef of its own.
+ */
+fn new_archipelago(seed: u64) -> Ocean {
+    let mut ocean = Ocean::with_seed(seed);
+    let shared: Vec<SharedReef> = (0..4).map(|_| ocean.generate_reef(5, 5, 5, 5)).collect();
+
+    for i in 0..8 {
+        let own = ocean.generate_reef(2, 2, 2, 2);
+        let mut beach = Beach::new();
+        for (j, diet) in [Diet::Fish, Diet::Shellfish, Diet::Plants]
+            .into_iter()
+            .enumerate()
+        {
+            let mut crab = Crab::new(format!("Crab {}-{}", i, j), 30, Color::new_red(), diet);
+            crab.discover_reef(SharedReef::clone(&shared[i / 2]));
+            crab.discover_reef(SharedReef::clone(&own));
+            beach.add_crab(crab);
+        }
+        ocean.add_beach(beach);
+    }
+    ocean
+}
+
+fn crab_energies(ocean: &Ocean) -> Vec<u32> {
+    ocean
+        .beaches()
+        .flat_map(|b| b.crabs())
+        .map(|c| c.energy())
+        .collect()
+}
+
+/* Tests */
+
+#[test]
+fn parallel_ocean_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Ocean>();
+    assert_send_sync::<Beach>();
+    assert_send_sync::<Crab>();
+    assert_send_sync::<SharedReef>();
+}
+
+#[test]
+fn parallel_ocean_deterministic_per_seed() {
+    let mut ocean1 = new_archipelago(5);
+    let mut ocean2 = new_archipelago(5);
+
+    assert_eq!(ocean1.run(50), ocean2.run(50));
+    assert_eq!(crab_energies(&ocean1), crab_energies(&ocean2));
+}
+
+#[test]
+fn parallel_oceans_on_threads() {
+    let handles: Vec<_> = (0..4)
+        .map(|seed| std::thread::spawn(move || new_archipelago(seed).run(20)))
+        .collect();
+    let reports: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+    for (seed, reports) in reports.iter().enumerate() {
+        assert_eq!(reports, &new_archipelago(seed as u64).run(20));
+    }
+}
@@ -0,0 +1,153 @@
+extern crate ocean;
+
+/*
+ * Run `cargo test pedigree` to run all of the lineage tests.
+ */
+
+use ocean::beach::*;
+use ocean::color::*;
+use ocean::crab::*;
+use ocean::diet::*;
+use ocean::error::*;
+
+use std::collections::BTreeSet;
+
+/* Fixtures */
+
+/**
+ * A beach with two founders (0 and 1), two of their children (2 and 3),
+ * and one grandchild (4) bred from those siblings.
+ */
+fn new_family_beach() -> Beach {
+    let mut beach = Beach::new();
+    beach.add_crab(Crab::new(
+        String::from("Adam"),
+        10,
+        Color::new_red(),
+        Diet::Fish,
+    ));
+    beach.add_crab(Crab::new(
+        String::from("Eve"),
+        10,
+        Color::new_blue(),
+        Diet::Fish,
+    ));
+    beach.breed_crabs(0, 1, String::from("Cain"));
+    beach.breed_crabs(0, 1, String::from("Awan"));
+    beach.breed_crabs(2, 3, String::from("Enoch"));
+    beach
+}
+
+fn ids(beach: &Beach) -> Vec<CrabId> {
+    beach.crabs().map(|c| c.id()).collect()
+}
+
+/* Tests */
+
+#[test]
+fn pedigree_crab_ids_unique_and_parents_recorded() {
+    let beach = new_family_beach();
+    let ids = ids(&beach);
+
+    let unique: BTreeSet<CrabId> = ids.iter().copied().collect();
+    assert_eq!(unique.len(), 5);
+
+    assert_eq!(beach.get_crab(0).parents(), None);
+    assert_eq!(beach.get_crab(2).parents(), Some((ids[0], ids[1])));
+    assert_eq!(beach.get_crab(4).parents(), Some((ids[2], ids[3])));
+}
+
+#[test]
+fn pedigree_ancestors_and_descendants() {
+    let beach = new_family_beach();
+    let ids = ids(&beach);
+    let pedigree = beach.pedigree();
+
+    let ancestors: Vec<CrabId> = pedigree.ancestors(ids[4]).into_iter().collect();
+    assert_eq!(ancestors, ids[0..4].to_vec());
+
+    let descendants: Vec<CrabId> = pedigree.descendants(ids[0]).into_iter().collect();
+    assert_eq!(descendants, ids[2..5].to_vec());
+
+    assert!(pedigree.descendants(ids[4]).is_empty());
+}
+
+#[test]
+fn pedigree_rejects_stranger_with_same_id() {
+    let mut beach = new_family_beach();
+    let adam = beach.get_crab(0).id();
+
+    let mut elsewhere = Beach::new();
+    elsewhere.add_crab(Crab::new(
+        String::from("Stranger"),
+        10,
+        Color::new_red(),
+        Diet::Fish,
+    ));
+    let stranger = elsewhere.remove_crab(0).unwrap();
+    assert_eq!(stranger.id(), adam);
+
+    assert_eq!(
+        beach.try_add_crab(stranger).unwrap_err().error,
+        OceanError::DuplicateCrabId { id: adam }
+    );
+    assert_eq!(beach.size(), 5);
+    assert_eq!(beach.pedigree().name(adam), Some("Adam"));
+}
+
+#[test]
+fn pedigree_generation_depth() {
+    let beach = new_family_beach();
+    let ids = ids(&beach);
+
+    let generations: Vec<usize> = ids
+        .iter()
+        .map(|&id| beach.pedigree().generation(id))
+        .collect();
+    assert_eq!(generations, vec![0, 0, 1, 1, 2]);
+}
+
+#[test]
+fn pedigree_generation_of_deeply_inbred_lineage() {
+    let mut beach = new_family_beach();
+
+    // Every generation is bred from a pair of siblings, so a naive recursion
+    // would visit each ancestor an exponential number of times.
+    beach.breed_crabs(2, 3, String::from("Irad"));
+    for _ in 0..100 {
+        let n = beach.size();
+        beach.breed_crabs(n - 2, n - 1, String::from("Sibling"));
+        beach.breed_crabs(n - 2, n - 1, String::from("Sibling"));
+    }
+
+    let last = beach.crabs().last().unwrap().id();
+    assert_eq!(beach.pedigree().generation(last), 102);
+}
+
+#[test]
+fn pedigree_inbreeding_coefficient() {
+    let beach = new_family_beach();
+    let ids = ids(&beach);
+    let pedigree = beach.pedigree();
+
+    // Founders and their children are not inbred...
+    assert_eq!(pedigree.inbreeding(ids[0]), 0.0);
+    assert_eq!(pedigree.inbreeding(ids[2]), 0.0);
+
+    // ... but the child of full siblings has an inbreeding coefficient of 1/4.
+    assert_eq!(pedigree.kinship(ids[2], ids[3]), 0.25);
+    assert_eq!(pedigree.inbreeding(ids[4]), 0.25);
+}
+
+#[test]
+fn pedigree_to_dot() {
+    let beach = new_family_beach();
+    let ids = ids(&beach);
+    let dot = beach.pedigree().to_dot();
+
+    assert!(dot.starts_with("digraph pedigree {\n"));
+    assert!(dot.contains(&format!("crab{} [label=\"Enoch\"];", ids[4])));
+    assert!(dot.contains(&format!("crab{} -> crab{};", ids[2], ids[4])));
+    assert!(dot.contains(&format!("crab{} -> crab{};", ids[3], ids[4])));
+    assert_eq!(dot.matches("->").count(), 6);
+}
@@ -0,0 +1,143 @@
+extern crate ocean;
+
+/*
+ * Run `cargo test prey_` to run all of the prey description tests.
+ */
+
+use ocean::color::*;
+use ocean::crab::*;
+use ocean::diet::*;
+use ocean::prey::*;
+use ocean::reef::*;
+use ocean::registry::*;
+
+/* Fixtures */
+
+struct Kelp {
+    height: u32,
+}
+
+impl Prey for Kelp {
+    fn diet(&self) -> Diet {
+        Diet::Plants
+    }
+
+    fn try_escape(&mut self, _crab: &Crab) -> bool {
+        false
+    }
+
+    fn nutrition(&self) -> u32 {
+        self.height
+    }
+
+    fn to_record(&self) -> PreyRecord {
+        PreyRecord::new("Kelp").with("height", self.height)
+    }
+}
+
+/* Tests */
+
+#[test]
+fn prey_kind_builtin() {
+    let prey: Vec<Box<dyn Prey>> = vec![
+        Box::new(Minnow::new(25)),
+        Box::new(Shrimp::new(1)),
+        Box::new(Clam::new()),
+        Box::new(Algae::new()),
+    ];
+    let kinds: Vec<PreyKind> = prey.iter().map(|p| p.kind()).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            PreyKind::Minnow,
+            PreyKind::Shrimp,
+            PreyKind::Clam,
+            PreyKind::Algae
+        ]
+    );
+    for p in &prey {
+        assert_eq!(PreyKind::from_tag(p.to_record().tag()), p.kind());
+    }
+}
+
+#[test]
+fn prey_kind_custom() {
+    let kelp: Box<dyn Prey> = Box::new(Kelp { height: 3 });
+    assert_eq!(kelp.kind(), PreyKind::Custom(String::from("Kelp")));
+    assert_eq!(kelp.describe(), "Kelp (height 3)");
+}
+
+#[test]
+fn prey_describe_state() {
+    let mut shrimp: Box<dyn Prey> = Box::new(Shrimp::new(1));
+    assert_eq!(shrimp.describe(), "Shrimp (energy 1)");
+
+    let crab = Crab::new(String::from("Prinz"), 20, Color::new_red(), Diet::Shellfish);
+    shrimp.try_escape(&crab);
+    assert_eq!(shrimp.to_string(), "Shrimp (energy 0)");
+
+    let minnow: Box<dyn Prey> = Box::new(Minnow::new(25));
+    assert_eq!(minnow.to_string(), "Minnow (speed 25)");
+    let clam: Box<dyn Prey> = Box::new(Clam::new());
+    assert_eq!(clam.to_string(), "Clam");
+}
+
+#[test]
+fn prey_debug_reef() {
+    let mut reef = Reef::new();
+    reef.add_prey(Box::new(Minnow::new(25)));
+    reef.add_prey(Box::new(Algae::new()));
+
+    let prey: Vec<&Box<dyn Prey>> = reef.prey().collect();
+    assert_eq!(format!("{:?}", prey), "[Minnow (speed 25), Algae]");
+}
+
+#[test]
+fn prey_freshness_spoil_rates() {
+    let mut prey: Vec<Box<dyn Prey>> = vec![
+        Box::new(Minnow::new(25)),
+        Box::new(Shrimp::new(1)),
+        Box::new(Clam::new()),
+        Box::new(Algae::new()),
+        Box::new(Kelp { height: 3 }),
+    ];
+    assert!(prey.iter().all(|p| p.freshness() == MAX_FRESHNESS));
+
+    for p in prey.iter_mut() {
+        for _ in 0..5 {
+            p.grow_older();
+        }
+    }
+    let freshness: Vec<u32> = prey.iter().map(|p| p.freshness()).collect();
+    assert_eq!(freshness, vec![50, 60, 80, 75, 100]);
+
+    // Fish spoil first; custom prey never do.
+    for p in prey.iter_mut() {
+        for _ in 0..5 {
+            p.grow_older();
+        }
+    }
+    let spoiled: Vec<bool> = prey.iter().map(|p| p.is_spoiled()).collect();
+    assert_eq!(spoiled, vec![true, false, false, false, false]);
+}
+
+#[test]
+fn prey_age_in_record() {
+    let mut minnow: Box<dyn Prey> = Box::new(Minnow::new(25));
+    assert_eq!(minnow.to_record().get("age"), None);
+
+    minnow.grow_older();
+    minnow.grow_older();
+    assert_eq!(minnow.to_string(), "Minnow (age 2, speed 25)");
+
+    let rebuilt = PreyRegistry::new().build(&minnow.to_record()).unwrap();
+    assert_eq!(rebuilt.age(), 2);
+    assert_eq!(rebuilt.freshness(), 80);
+}
+
+#[test]
+fn prey_huge_nutrition_fills_crab() {
+    let mut crab = Crab::new(String::from("Pinchy"), 5, Color::new_red(), Diet::Plants);
+    crab.eat(Box::new(Kelp { height: u32::MAX }));
+    assert_eq!(crab.energy(), Crab::MAX_ENERGY);
+}
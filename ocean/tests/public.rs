@@ -12,9 +12,7 @@ use ocean::diet::*;
 use ocean::ocean::*;
 use ocean::prey::*;
 use ocean::reef::*;
-
-use std::cell::RefCell;
-use std::rc::Rc;
+use ocean::shared::{self, SharedReef};
 
 /* Fixtures */
 
@@ -131,7 +129,7 @@ fn part1_beach_fastest_fastest_first() {
 
     match beach.get_fastest_crab() {
         Some(c) => assert_eq!(c.name(), SEBASTIAN),
-        None => assert!(false),
+        None => panic!("expected a fastest crab"),
     }
 
     // Now let's add a slower crab...
@@ -140,7 +138,7 @@ fn part1_beach_fastest_fastest_first() {
 
     match beach.get_fastest_crab() {
         Some(c) => assert_eq!(c.name(), SEBASTIAN),
-        None => assert!(false),
+        None => panic!("expected a fastest crab"),
     }
 }
 
@@ -157,7 +155,7 @@ fn part1_beach_fastest_fastest_second() {
 
     match beach.get_fastest_crab() {
         Some(c) => assert_eq!(c.name(), PRINZ),
-        None => assert!(false),
+        None => panic!("expected a fastest crab"),
     }
 
     // Now let's add a faster crab...
@@ -166,7 +164,7 @@ fn part1_beach_fastest_fastest_second() {
 
     match beach.get_fastest_crab() {
         Some(c) => assert_eq!(c.name(), SEBASTIAN),
-        None => assert!(false),
+        None => panic!("expected a fastest crab"),
     }
 }
 
@@ -257,78 +255,78 @@ fn part2_reef_add_take_prey() {
 #[test]
 fn part2_crab_discover_reefs() {
     let mut crab = new_prinz();
-    let reef = Rc::new(RefCell::new(Reef::new()));
+    let reef = shared::share(Reef::new());
 
     // Only one reference exists: from this scope.
-    assert_eq!(Rc::strong_count(&reef), 1);
+    assert_eq!(SharedReef::strong_count(&reef), 1);
 
     // Create a 2nd reference to the reef, and give it to `crab.discover_reef`.
-    crab.discover_reef(Rc::clone(&reef));
+    crab.discover_reef(SharedReef::clone(&reef));
 
     // Now there are two references to the reef.
-    assert_eq!(Rc::strong_count(&reef), 2);
+    assert_eq!(SharedReef::strong_count(&reef), 2);
 }
 
 #[test]
 fn part2_crab_hunt_empty_reef() {
     let mut crab = new_prinz();
-    let reef = Rc::new(RefCell::new(Reef::new()));
+    let reef = shared::share(Reef::new());
 
     crab.discover_reef(reef);
-    assert_eq!(crab.hunt(), false);
+    assert!(!crab.hunt());
 }
 
 #[test]
 fn part2_crab_hunt_success() {
     let mut crab = new_prinz();
-    let reef = Rc::new(RefCell::new(Reef::new()));
+    let reef = shared::share(Reef::new());
 
-    reef.borrow_mut().add_prey(Box::new(Clam::new()));
-    assert_eq!(reef.borrow().population(), 1);
+    shared::write(&reef).add_prey(Box::new(Clam::new()));
+    assert_eq!(shared::read(&reef).population(), 1);
 
     // Prinz succeeded in hunting...
-    crab.discover_reef(Rc::clone(&reef));
-    assert_eq!(crab.hunt(), true);
+    crab.discover_reef(SharedReef::clone(&reef));
+    assert!(crab.hunt());
 
     // ... and the reef should now be empty.
-    assert_eq!(reef.borrow().population(), 0);
+    assert_eq!(shared::read(&reef).population(), 0);
 }
 
 #[test]
 fn part2_crab_hunt_incompatible_diet() {
     let mut crab = new_prinz();
-    let reef = Rc::new(RefCell::new(Reef::new()));
+    let reef = shared::share(Reef::new());
 
     // Prinz eats shellfish, but not plants.
-    reef.borrow_mut().add_prey(Box::new(Algae::new()));
-    assert_eq!(reef.borrow().population(), 1);
+    shared::write(&reef).add_prey(Box::new(Algae::new()));
+    assert_eq!(shared::read(&reef).population(), 1);
 
     // Prinz failed in hunting...
-    crab.discover_reef(Rc::clone(&reef));
-    assert_eq!(crab.hunt(), false);
+    crab.discover_reef(SharedReef::clone(&reef));
+    assert!(!crab.hunt());
 
     // ... and the reef still contains the algae, which was released back to it.
-    assert_eq!(reef.borrow().population(), 1);
+    assert_eq!(shared::read(&reef).population(), 1);
 }
 
 #[test]
 fn part2_crab_hunt_escaped_prey() {
     let mut crab = new_prinz();
-    let reef = Rc::new(RefCell::new(Reef::new()));
+    let reef = shared::share(Reef::new());
 
     // Prinz eats shellfish, but this Shrimp will be able to escape (once).
-    reef.borrow_mut().add_prey(Box::new(Shrimp::new(1)));
-    assert_eq!(reef.borrow().population(), 1);
+    shared::write(&reef).add_prey(Box::new(Shrimp::new(1)));
+    assert_eq!(shared::read(&reef).population(), 1);
 
-    crab.discover_reef(Rc::clone(&reef));
+    crab.discover_reef(SharedReef::clone(&reef));
 
     // The first hunt fails. The Shrimp escapes.
-    assert_eq!(crab.hunt(), false);
-    assert_eq!(reef.borrow().population(), 1);
+    assert!(!crab.hunt());
+    assert_eq!(shared::read(&reef).population(), 1);
 
     // The second hunt succeeds. The Shrimp has no energy left.
-    assert_eq!(crab.hunt(), true);
-    assert_eq!(reef.borrow().population(), 0);
+    assert!(crab.hunt());
+    assert_eq!(shared::read(&reef).population(), 0);
 }
 
 #[test]
@@ -341,18 +339,18 @@ fn part2_ocean_generate_algae_only() {
         // Generate a reef with that much algae only, in one reef.
         let reef = ocean.generate_reef(0, 0, 0, n_algae);
         assert_eq!(ocean.reefs().len(), 1);
-        assert_eq!(reef.borrow().population(), n_algae as usize);
+        assert_eq!(shared::read(&reef).population(), n_algae as usize);
 
         // Prinz does not eat algae, so he should never succeed
         // hunting in a reef with only algae. Poor Prinz...
         let mut prinz = new_prinz();
-        prinz.discover_reef(Rc::clone(&reef));
-        assert_eq!(prinz.hunt(), false);
+        prinz.discover_reef(SharedReef::clone(&reef));
+        assert!(!prinz.hunt());
 
         // But Sebastian eats algae, so he should be fine.
         let mut sebastian = new_sebastian();
-        sebastian.discover_reef(Rc::clone(&reef));
-        assert_eq!(sebastian.hunt(), true);
+        sebastian.discover_reef(SharedReef::clone(&reef));
+        assert!(sebastian.hunt());
     }
 }
 
@@ -366,16 +364,16 @@ fn part2_ocean_generate_algae_bountiful() {
         // Generate a reef with that much algae only, in one reef.
         let reef = ocean.generate_reef(n, n, n, n);
         assert_eq!(ocean.reefs().len(), 1);
-        assert_eq!(reef.borrow().population(), 4 * (n as usize));
+        assert_eq!(shared::read(&reef).population(), 4 * (n as usize));
 
         // Prinz eats clams and shrimp, so he should be fine.
         let mut prinz = new_prinz();
-        prinz.discover_reef(Rc::clone(&reef));
-        assert_eq!(prinz.hunt(), true);
+        prinz.discover_reef(SharedReef::clone(&reef));
+        assert!(prinz.hunt());
 
         // Sebastian eats algae, so he should be fine.
         let mut sebastian = new_sebastian();
-        sebastian.discover_reef(Rc::clone(&reef));
-        assert_eq!(sebastian.hunt(), true);
+        sebastian.discover_reef(SharedReef::clone(&reef));
+        assert!(sebastian.hunt());
     }
 }
@@ -0,0 +1,90 @@
+extern crate ocean;
+
+/*
+ * Run `cargo test rng` to run all of the random number generation tests.
+ */
+
+use ocean::beach::*;
+use ocean::color::*;
+use ocean::crab::*;
+use ocean::diet::*;
+use ocean::ocean::*;
+use ocean::rand::*;
+
+/* Fixtures */
+
+fn new_parents() -> Beach {
+    let mut beach = Beach::new();
+    beach.add_crab(Crab::new(
+        String::from("A"),
+        5,
+        Color::new_red(),
+        Diet::Fish,
+    ));
+    beach.add_crab(Crab::new(
+        String::from("B"),
+        5,
+        Color::new_blue(),
+        Diet::Plants,
+    ));
+    beach
+}
+
+fn bred_diets(ocean: &mut Ocean, n: usize) -> Vec<Diet> {
+    let beach = ocean.beaches_mut().next().unwrap();
+    for i in 0..n {
+        beach.breed_crabs(0, 1, format!("Baby {}", i));
+    }
+    beach.crabs().skip(2).map(|c| c.diet()).collect()
+}
+
+/* Tests */
+
+#[test]
+fn rng_same_seed_same_stream() {
+    let mut a = OceanRng::new(42);
+    let mut b = OceanRng::new(42);
+    for _ in 0..100 {
+        assert_eq!(a.next_u32(), b.next_u32());
+    }
+}
+
+#[test]
+fn rng_reseed_restarts_stream() {
+    let mut rng = OceanRng::new(7);
+    let first: Vec<u32> = (0..10).map(|_| rng.next_u32()).collect();
+
+    rng.reseed(7);
+    let again: Vec<u32> = (0..10).map(|_| rng.next_u32()).collect();
+    assert_eq!(first, again);
+    assert_eq!(rng.seed(), 7);
+}
+
+#[test]
+fn rng_fork_is_independent_and_reproducible() {
+    let mut parent = OceanRng::new(1);
+    let mut child = parent.fork();
+    assert_ne!(child.seed(), parent.seed());
+
+    let mut parent_again = OceanRng::new(1);
+    let mut child_again = parent_again.fork();
+    assert_eq!(child.next_u64(), child_again.next_u64());
+    assert_eq!(parent.next_u64(), parent_again.next_u64());
+}
+
+#[test]
+fn rng_ocean_breeding_reproducible_per_seed() {
+    let mut ocean1 = Ocean::with_seed(3);
+    ocean1.add_beach(new_parents());
+    let mut ocean2 = Ocean::with_seed(3);
+    ocean2.add_beach(new_parents());
+
+    // Interleaving the two simulations does not affect either of them.
+    let diets1 = bred_diets(&mut ocean1, 20);
+    let diets2 = bred_diets(&mut ocean2, 20);
+    assert_eq!(diets1, diets2);
+
+    let mut ocean3 = Ocean::with_seed(4);
+    ocean3.add_beach(new_parents());
+    assert_ne!(diets1, bred_diets(&mut ocean3, 20));
+}
@@ -0,0 +1,221 @@
+extern crate ocean;
+
+/*
+ * Run `cargo test reef_` to run all of the reef regrowth tests.
+ */
+
+use ocean::crab::*;
+use ocean::diet::*;
+use ocean::distribution::*;
+use ocean::error::*;
+use ocean::ocean::*;
+use ocean::prey::*;
+use ocean::rand::*;
+use ocean::reef::*;
+use ocean::shared::*;
+
+/* Fixtures */
+
+struct Kelp {
+    height: u32,
+}
+
+impl Prey for Kelp {
+    fn diet(&self) -> Diet {
+        Diet::Plants
+    }
+
+    fn try_escape(&mut self, _crab: &Crab) -> bool {
+        false
+    }
+
+    fn nutrition(&self) -> u32 {
+        self.height
+    }
+
+    fn to_record(&self) -> PreyRecord {
+        PreyRecord::new("Kelp").with("height", self.height)
+    }
+}
+
+fn build_kelp(record: &PreyRecord) -> Option<Box<dyn Prey>> {
+    Some(Box::new(Kelp {
+        height: record.get("height")?,
+    }))
+}
+
+fn params(reef: &SharedReef, tag: &str, param: &str) -> Vec<u32> {
+    read(reef)
+        .prey()
+        .map(|p| p.to_record())
+        .filter(|r| r.tag() == tag)
+        .map(|r| r.get(param).unwrap())
+        .collect()
+}
+
+/* Tests */
+
+#[test]
+fn reef_regenerate_algae_regrows() {
+    let mut rng = OceanRng::new(0);
+    let mut reef = Reef::new();
+    reef.add_prey(Box::new(Algae::new()));
+
+    let born: usize = (0..20).map(|_| reef.regenerate(&mut rng)).sum();
+    assert!(born > 0);
+    assert_eq!(reef.population(), 1 + born);
+}
+
+#[test]
+fn reef_regenerate_needs_pairs() {
+    let mut rng = OceanRng::new(0);
+
+    // A lone minnow (or shrimp) cannot breed...
+    let mut reef = Reef::new();
+    reef.add_prey(Box::new(Minnow::new(25)));
+    reef.add_prey(Box::new(Shrimp::new(1)));
+    for _ in 0..100 {
+        assert_eq!(reef.regenerate(&mut rng), 0);
+    }
+
+    // ... but a pair of them can, and their offspring take after them.
+    reef.add_prey(Box::new(Minnow::new(25)));
+    let born: usize = (0..100).map(|_| reef.regenerate(&mut rng)).sum();
+    assert!(born > 0);
+    assert!(reef
+        .prey()
+        .all(|p| p.to_record().tag() != "minnow" || p.to_record().get("speed") == Some(25)));
+}
+
+#[test]
+fn reef_regenerate_respects_capacity() {
+    let mut rng = OceanRng::new(0);
+    let mut reef = Reef::with_capacity(15);
+    for _ in 0..10 {
+        reef.add_prey(Box::new(Algae::new()));
+    }
+
+    for _ in 0..100 {
+        reef.regenerate(&mut rng);
+    }
+    assert_eq!(reef.population(), 15);
+}
+
+#[test]
+fn reef_ocean_step_regrows_reefs() {
+    let mut ocean = Ocean::new();
+    ocean.generate_reef(0, 0, 0, 10);
+
+    let reports = ocean.run(10);
+    let born: usize = reports.iter().map(|r| r.prey_born()).sum();
+    assert!(born > 0);
+    assert_eq!(reports.last().unwrap().prey_remaining(), 10 + born);
+}
+
+#[test]
+fn reef_spec_generates_counts() {
+    let mut ocean = Ocean::new();
+    let spec = ReefSpec::new()
+        .capacity(50)
+        .minnows(3, Distribution::Fixed(30))
+        .shrimp(2, Distribution::Fixed(4))
+        .clams(1)
+        .algae(4);
+    let reef = ocean.generate_reef_from(&spec).unwrap();
+
+    assert_eq!(ocean.reefs().len(), 1);
+    assert_eq!(read(&reef).population(), 10);
+    assert_eq!(read(&reef).capacity(), 50);
+    assert_eq!(params(&reef, "minnow", "speed"), vec![30, 30, 30]);
+    assert_eq!(params(&reef, "shrimp", "energy"), vec![4, 4]);
+}
+
+#[test]
+fn reef_spec_samples_distributions() {
+    let mut ocean = Ocean::new();
+    let spec = ReefSpec::new()
+        .minnows(100, Distribution::Uniform { min: 10, max: 20 })
+        .shrimp(
+            100,
+            Distribution::Normal {
+                mean: 50.0,
+                std_dev: 5.0,
+            },
+        );
+    let reef = ocean.generate_reef_from(&spec).unwrap();
+
+    let speeds = params(&reef, "minnow", "speed");
+    assert!(speeds.iter().all(|s| (10..=20).contains(s)));
+    assert!(speeds.iter().any(|&s| s != speeds[0]));
+
+    let energies = params(&reef, "shrimp", "energy");
+    let mean = energies.iter().sum::<u32>() as f64 / energies.len() as f64;
+    assert!((45.0..55.0).contains(&mean));
+}
+
+#[test]
+fn reef_spec_is_deterministic_per_seed() {
+    let spec = ReefSpec::new().minnows(
+        20,
+        Distribution::Normal {
+            mean: 25.0,
+            std_dev: 5.0,
+        },
+    );
+    let speeds = |seed| {
+        let mut ocean = Ocean::with_seed(seed);
+        let reef = ocean.generate_reef_from(&spec).unwrap();
+        params(&reef, "minnow", "speed")
+    };
+    assert_eq!(speeds(7), speeds(7));
+    assert_ne!(speeds(7), speeds(8));
+}
+
+#[test]
+fn reef_spec_custom_prey() {
+    let mut ocean = Ocean::new();
+    ocean.registry_mut().register("Kelp", build_kelp);
+    let spec = ReefSpec::new().species(
+        "Kelp",
+        5,
+        [("height", Distribution::Uniform { min: 1, max: 3 })],
+    );
+    let reef = ocean.generate_reef_from(&spec).unwrap();
+
+    let heights = params(&reef, "Kelp", "height");
+    assert_eq!(heights.len(), 5);
+    assert!(heights.iter().all(|h| (1..=3).contains(h)));
+}
+
+#[test]
+fn reef_spec_rejects_unknown_prey() {
+    let mut ocean = Ocean::new();
+    let unknown = ReefSpec::new().species("Kelp", 1, [("height", Distribution::Fixed(1))]);
+    assert_eq!(
+        ocean.generate_reef_from(&unknown).unwrap_err(),
+        OceanError::UnknownPrey {
+            tag: String::from("Kelp")
+        }
+    );
+
+    ocean.registry_mut().register("Kelp", build_kelp);
+    let missing = ReefSpec::new().species("Kelp", 1, []);
+    assert_eq!(
+        ocean.generate_reef_from(&missing).unwrap_err(),
+        OceanError::InvalidPrey {
+            tag: String::from("Kelp")
+        }
+    );
+    assert_eq!(ocean.reefs().len(), 0);
+}
+
+#[test]
+fn reef_distribution_uniform_covers_range() {
+    let mut rng = OceanRng::new(0);
+    let d = Distribution::Uniform { min: 1, max: 3 };
+    let mut seen = [false; 4];
+    for _ in 0..100 {
+        seen[d.sample(&mut rng) as usize] = true;
+    }
+    assert_eq!(seen, [false, true, true, true]);
+}
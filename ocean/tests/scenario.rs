@@ -0,0 +1,182 @@
+extern crate ocean;
+
+/*
+ * Run `cargo test --features serde scenario` to run all of the scenario tests.
+ */
+
+use ocean::color::*;
+use ocean::diet::*;
+use ocean::grid::*;
+use ocean::scenario::*;
+
+/* Fixtures */
+
+fn new_scenario() -> Scenario {
+    Scenario {
+        seed: 1,
+        reefs: vec![
+            ReefScenario {
+                clams: 3,
+                ..ReefScenario::default()
+            },
+            ReefScenario {
+                algae: 2,
+                ..ReefScenario::default()
+            },
+        ],
+        beaches: vec![BeachScenario {
+            crabs: vec![CrabScenario {
+                name: String::from("Prinz"),
+                speed: 20,
+                color: Color::new(255, 215, 0),
+                diet: Diet::Shellfish,
+                diets: None,
+                reefs: vec![0],
+            }],
+            ..BeachScenario::default()
+        }],
+        ..Scenario::default()
+    }
+}
+
+/* Tests */
+
+#[test]
+fn scenario_build() {
+    let mut ocean = new_scenario().build().unwrap();
+    assert_eq!(ocean.reefs().len(), 2);
+    assert_eq!(ocean.beaches().next().unwrap().size(), 1);
+
+    let reports = ocean.run(3);
+    let fed: usize = reports.iter().map(|r| r.fed()).sum();
+    assert_eq!(fed, 3);
+}
+
+#[test]
+fn scenario_build_unknown_reef() {
+    let mut scenario = new_scenario();
+    scenario.beaches[0].crabs[0].reefs.push(7);
+
+    let err = scenario.build().unwrap_err();
+    assert_eq!(
+        err,
+        ScenarioError::UnknownReef {
+            crab: String::from("Prinz"),
+            reef: 7
+        }
+    );
+}
+
+#[test]
+fn scenario_build_unreachable_reef() {
+    let mut scenario = new_scenario();
+    scenario.reefs[0].position = Position::new(0, 30);
+    scenario.beaches[0].position = Position::new(0, -15);
+
+    let err = scenario.build().unwrap_err();
+    assert_eq!(
+        err,
+        ScenarioError::UnreachableReef {
+            crab: String::from("Prinz"),
+            reef: 0
+        }
+    );
+
+    // Within 40 of the beach, the speed-20 crab can reach it.
+    scenario.beaches[0].position = Position::new(0, -5);
+    let ocean = scenario.build().unwrap();
+    let beach = ocean.beaches().next().unwrap();
+    assert_eq!(beach.position(), Position::new(0, -5));
+    assert_eq!(beach.get_crab(0).home(), Position::new(0, -5));
+}
+
+#[test]
+fn scenario_build_over_capacity() {
+    let mut scenario = new_scenario();
+    scenario.beaches[0].capacity = Some(0);
+
+    let err = scenario.build().unwrap_err();
+    assert_eq!(
+        err,
+        ScenarioError::OverCapacity {
+            beach: 0,
+            capacity: 0
+        }
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn scenario_from_json() {
+    let json = r#"{
+        "seed": 1,
+        "reefs": [{ "clams": 3 }, { "algae": 2 }],
+        "beaches": [{
+            "crabs": [{
+                "name": "Prinz",
+                "speed": 20,
+                "color": { "r": 255, "g": 215, "b": 0 },
+                "diet": "Shellfish",
+                "reefs": [0]
+            }]
+        }]
+    }"#;
+    assert_eq!(Scenario::from_json(json).unwrap(), new_scenario());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn scenario_from_json_omnivore() {
+    let json = r#"{
+        "reefs": [{ "algae": 1 }],
+        "beaches": [{
+            "crabs": [{
+                "name": "Omni",
+                "speed": 20,
+                "color": { "r": 0, "g": 0, "b": 255 },
+                "diet": "Shellfish",
+                "diets": { "Shellfish": 2, "Plants": 1 },
+                "reefs": [0]
+            }]
+        }]
+    }"#;
+    let mut ocean = Scenario::from_json(json).unwrap().build().unwrap();
+    let crab = ocean.beaches().next().unwrap().get_crab(0);
+    assert_eq!(
+        crab.diets(),
+        &(Diet::Shellfish | Diet::Plants).with(Diet::Shellfish, 2)
+    );
+    assert_eq!(ocean.step().fed(), 1);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn scenario_ocean_sim_writes_csv() {
+    use ocean::report::TickReport;
+    use std::process::Command;
+
+    let dir = std::env::temp_dir();
+    let csv = dir.join(format!("ocean-sim-{}.csv", std::process::id()));
+    let output = Command::new(env!("CARGO_BIN_EXE_ocean-sim"))
+        .arg(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/scenarios/example.json"
+        ))
+        .args(["--ticks", "5", "--seed", "3", "--csv"])
+        .arg(&csv)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("Simulated 5 ticks (seed 3)"));
+    for line in ["died of old age:", "migrated:", "evicted:"] {
+        assert!(stdout.contains(line));
+    }
+
+    let contents = std::fs::read_to_string(&csv).unwrap();
+    std::fs::remove_file(&csv).unwrap();
+    let lines: Vec<&str> = contents.lines().collect();
+    assert_eq!(lines.len(), 6);
+    assert_eq!(lines[0], TickReport::CSV_HEADER);
+    assert!(lines[1].starts_with("1,2,"));
+}
@@ -0,0 +1,117 @@
+extern crate ocean;
+
+/*
+ * Run `cargo test sim` to run all of the simulation tests.
+ */
+
+use ocean::beach::*;
+use ocean::color::*;
+use ocean::crab::*;
+use ocean::diet::*;
+use ocean::ocean::*;
+use ocean::shared::SharedReef;
+
+/* Fixtures */
+
+fn new_clam_eater(name: &str) -> Crab {
+    Crab::new(String::from(name), 20, Color::new_blue(), Diet::Shellfish)
+}
+
+/* Tests */
+
+#[test]
+fn sim_ocean_step_empty() {
+    let mut ocean = Ocean::new();
+
+    let report = ocean.step();
+    assert_eq!(report.tick(), 1);
+    assert_eq!(report.crabs(), 0);
+    assert_eq!(ocean.tick(), 1);
+}
+
+#[test]
+fn sim_ocean_step_feeds_crabs() {
+    let mut ocean = Ocean::new();
+    let reef = ocean.generate_reef(0, 0, 1, 0);
+
+    let mut beach = Beach::new();
+    let mut crab = new_clam_eater("Clawdia");
+    crab.discover_reef(SharedReef::clone(&reef));
+    beach.add_crab(crab);
+    let mut crab = new_clam_eater("Pinchy");
+    crab.discover_reef(SharedReef::clone(&reef));
+    beach.add_crab(crab);
+    ocean.add_beach(beach);
+
+    // Only one clam: the first crab on the beach gets it.
+    let report = ocean.step();
+    assert_eq!(report.crabs(), 2);
+    assert_eq!(report.fed(), 1);
+    assert_eq!(report.hungry(), 1);
+    assert_eq!(report.prey_remaining(), 0);
+
+    let beach = ocean.beaches().next().unwrap();
+    assert!(!beach.get_crab(0).is_hungry());
+    assert!(beach.get_crab(1).is_hungry());
+}
+
+#[test]
+fn sim_ocean_run_reports_each_tick() {
+    let mut ocean = Ocean::new();
+    let reef = ocean.generate_reef(0, 0, 2, 0);
+
+    let mut beach = Beach::new();
+    let mut crab = new_clam_eater("Clawdia");
+    crab.discover_reef(reef);
+    beach.add_crab(crab);
+    ocean.add_beach(beach);
+
+    let reports = ocean.run(3);
+    let ticks: Vec<u64> = reports.iter().map(|r| r.tick()).collect();
+    let fed: Vec<usize> = reports.iter().map(|r| r.fed()).collect();
+    assert_eq!(ticks, vec![1, 2, 3]);
+    assert_eq!(fed, vec![1, 1, 0]);
+}
+
+#[test]
+fn sim_crab_eating_restores_energy() {
+    let mut ocean = Ocean::new();
+    let reef = ocean.generate_reef(0, 0, 1, 0);
+
+    let mut crab = new_clam_eater("Clawdia");
+    crab.discover_reef(reef);
+    crab.metabolize();
+    assert_eq!(crab.energy(), Crab::INITIAL_ENERGY - Crab::METABOLISM);
+
+    assert!(crab.hunt());
+    assert_eq!(crab.energy(), Crab::INITIAL_ENERGY - Crab::METABOLISM + 4);
+}
+
+#[test]
+fn sim_crab_energy_capped() {
+    let mut ocean = Ocean::new();
+    let reef = ocean.generate_reef(0, 0, 10, 0);
+
+    let mut crab = new_clam_eater("Clawdia");
+    crab.discover_reef(reef);
+    for _ in 0..10 {
+        crab.hunt();
+    }
+    assert_eq!(crab.energy(), Crab::MAX_ENERGY);
+}
+
+#[test]
+fn sim_ocean_step_starves_crabs() {
+    let mut ocean = Ocean::new();
+
+    let mut beach = Beach::new();
+    beach.add_crab(new_clam_eater("Clawdia"));
+    ocean.add_beach(beach);
+
+    // With nothing to eat, the crab survives exactly as long as its energy lasts.
+    let reports = ocean.run(u64::from(Crab::INITIAL_ENERGY / Crab::METABOLISM));
+    let starved: usize = reports.iter().map(|r| r.starved()).sum();
+    assert_eq!(starved, 1);
+    assert_eq!(reports.last().unwrap().starved(), 1);
+    assert_eq!(ocean.beaches().next().unwrap().size(), 0);
+}
@@ -0,0 +1,354 @@
+#![cfg(feature = "serde")]
+
+extern crate ocean;
+
+/*
+ * Run `cargo test --features serde snapshot` to run all of the serialization tests.
+ */
+
+use ocean::beach::*;
+use ocean::color::*;
+use ocean::cookbook::*;
+use ocean::crab::*;
+use ocean::diet::*;
+use ocean::distribution::*;
+use ocean::exploration::*;
+use ocean::grid::*;
+use ocean::migration::*;
+use ocean::ocean::*;
+use ocean::prey::*;
+use ocean::reef::*;
+use ocean::registry::*;
+use ocean::shared::{self, SharedReef};
+use ocean::snapshot::*;
+use ocean::strategy::*;
+
+/* Fixtures */
+
+struct Kelp {
+    height: u32,
+}
+
+impl Prey for Kelp {
+    fn diet(&self) -> Diet {
+        Diet::Plants
+    }
+
+    fn try_escape(&mut self, _crab: &Crab) -> bool {
+        false
+    }
+
+    fn nutrition(&self) -> u32 {
+        self.height
+    }
+
+    fn to_record(&self) -> PreyRecord {
+        PreyRecord::new("kelp").with("height", self.height)
+    }
+}
+
+#[derive(Debug)]
+struct Wander;
+
+impl HuntStrategy for Wander {
+    fn choose_reef(&mut self, populations: &[usize], _attempts: u32) -> Option<usize> {
+        populations.iter().rposition(|&n| n > 0)
+    }
+
+    fn to_record(&self) -> StrategyRecord {
+        StrategyRecord::new("wander")
+    }
+}
+
+/**
+ * An ocean with one beach of two crabs sharing the ocean's only reef, where the
+ * second crab also knows about a secret reef the ocean does not own, and
+ * hunts with a strategy that remembers where it last caught something. The
+ * first crab keeps a clam in its pantry, not quite enough to cook chowder.
+ */
+fn new_ocean() -> Ocean {
+    let mut ocean = Ocean::with_seed(9);
+    ocean.set_cookbook(Some(Cookbook::new()));
+    ocean.set_exploration(Some(ExplorationRates::default()));
+    ocean.set_migration(Some(MigrationPolicy::default()));
+    let reef = ocean.generate_reef(1, 2, 3, 0);
+    let secret = shared::share(Reef::new());
+    shared::write(&secret).add_prey(Box::new(Shrimp::new(4)));
+    shared::write(&secret).set_position(Position::new(3, 4));
+
+    let mut beach = Beach::new();
+    beach.set_position(Position::new(0, 1));
+    let mut c1 = Crab::new(
+        String::from("Prinz"),
+        20,
+        Color::new(255, 215, 0),
+        Diet::Shellfish,
+    )
+    .with_diets(Diet::Shellfish | Diet::Plants)
+    .with_lifespan(60);
+    c1.discover_reef(SharedReef::clone(&reef));
+    c1.pantry_mut().add_prey(Box::new(Clam::new()));
+    let mut c2 = Crab::new(String::from("Sebastian"), 30, Color::new_red(), Diet::Fish)
+        .with_strategy(Box::new(RememberSuccess::new()));
+    c2.discover_reef(SharedReef::clone(&reef));
+    c2.discover_reef(secret);
+    beach.add_crab(c1);
+    beach.add_crab(c2);
+    beach.breed_crabs(0, 1, String::from("Junior"));
+    beach.set_capacity(Some(10));
+    beach.set_overflow(Overflow::EvictWeakest);
+    beach.set_crowding_penalty(0.5);
+    ocean.add_beach(beach);
+
+    ocean.step();
+    ocean.set_lifespan(Some(Distribution::Normal {
+        mean: 50.0,
+        std_dev: 5.0,
+    }));
+    ocean
+}
+
+fn records(ocean: &Ocean) -> Vec<PreyRecord> {
+    ocean
+        .reefs()
+        .flat_map(|r| {
+            shared::read(r)
+                .prey()
+                .map(|p| p.to_record())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn assert_same(a: &Ocean, b: &Ocean) {
+    assert_eq!(a.tick(), b.tick());
+    assert_eq!(
+        a.cookbook().map(|c| c.recipes().len()),
+        b.cookbook().map(|c| c.recipes().len())
+    );
+    assert_eq!(a.exploration(), b.exploration());
+    assert_eq!(a.migration(), b.migration());
+    assert_eq!(a.lifespan(), b.lifespan());
+    assert_eq!(records(a), records(b));
+    let positions = |o: &Ocean| {
+        o.reefs()
+            .map(|r| shared::read(r).position())
+            .chain(o.beaches().map(|b| b.position()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(positions(a), positions(b));
+    let crowding = |o: &Ocean| {
+        o.beaches()
+            .map(|b| (b.capacity(), b.overflow(), b.crowding_penalty()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(crowding(a), crowding(b));
+
+    let crabs_a: Vec<&Crab> = a.beaches().flat_map(|b| b.crabs()).collect();
+    let crabs_b: Vec<&Crab> = b.beaches().flat_map(|b| b.crabs()).collect();
+    assert_eq!(crabs_a.len(), crabs_b.len());
+    for (x, y) in crabs_a.iter().zip(crabs_b.iter()) {
+        assert_eq!(x.id(), y.id());
+        assert_eq!(x.parents(), y.parents());
+        assert_eq!(x.name(), y.name());
+        assert_eq!(x.speed(), y.speed());
+        assert_eq!(x.genome(), y.genome());
+        assert_eq!(x.diets(), y.diets());
+        assert_eq!(x.home(), y.home());
+        assert_eq!(x.reefs().len(), y.reefs().len());
+        for i in 0..x.reefs().len() {
+            assert_eq!(x.reef_empty_ticks(i), y.reef_empty_ticks(i));
+        }
+        let pantry = |c: &Crab| {
+            c.pantry()
+                .prey_with_ages()
+                .map(|(p, age)| (p.to_record(), age))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(pantry(x), pantry(y));
+        assert_eq!(x.energy(), y.energy());
+        assert_eq!(x.age(), y.age());
+        assert_eq!(x.lifespan(), y.lifespan());
+        assert_eq!(x.strategy().to_record(), y.strategy().to_record());
+    }
+}
+
+/* Tests */
+
+#[test]
+fn snapshot_json_round_trip() {
+    let ocean = new_ocean();
+    let json = ocean.to_json().unwrap();
+    let loaded = Ocean::from_json(&json).unwrap();
+
+    assert_same(&ocean, &loaded);
+    assert_eq!(loaded.to_json().unwrap(), json);
+}
+
+#[test]
+fn snapshot_binary_round_trip() {
+    let ocean = new_ocean();
+    let bytes = ocean.to_bytes().unwrap();
+    let loaded = Ocean::from_bytes(&bytes).unwrap();
+
+    assert_same(&ocean, &loaded);
+    assert!(bytes.len() < ocean.to_json().unwrap().len());
+}
+
+#[test]
+fn snapshot_preserves_shared_reefs() {
+    let mut loaded = Ocean::from_json(&new_ocean().to_json().unwrap()).unwrap();
+
+    // One reference from this scope, one from the ocean, and one from each of the two hunters.
+    let reef = SharedReef::clone(loaded.reefs().next().unwrap());
+    assert_eq!(SharedReef::strong_count(&reef), 4);
+
+    // The crabs hunt in the very same reef the ocean owns.
+    let before = shared::read(&reef).population();
+    loaded.step();
+    assert!(shared::read(&reef).population() < before);
+}
+
+#[test]
+fn snapshot_continues_identically() {
+    let mut ocean = new_ocean();
+    let mut loaded = Ocean::from_bytes(&ocean.to_bytes().unwrap()).unwrap();
+
+    assert_eq!(ocean.run(5), loaded.run(5));
+    assert_same(&ocean, &loaded);
+}
+
+#[test]
+fn snapshot_custom_prey_registry() {
+    let mut ocean = Ocean::new();
+    let reef = ocean.generate_reef(0, 0, 0, 0);
+    shared::write(&reef).add_prey(Box::new(Kelp { height: 7 }));
+    let json = ocean.to_json().unwrap();
+
+    // The default registry does not know about kelp...
+    assert!(matches!(
+        Ocean::from_json(&json),
+        Err(SnapshotError::InvalidPrey(_))
+    ));
+
+    // ... but a registry with kelp registered does.
+    let mut registry = PreyRegistry::new();
+    registry.register("kelp", |r| {
+        Some(Box::new(Kelp {
+            height: r.get("height")?,
+        }))
+    });
+    let loaded = Ocean::from_json_with(&json, &registry).unwrap();
+    assert_eq!(
+        records(&loaded),
+        vec![PreyRecord::new("kelp").with("height", 7)]
+    );
+
+    // The loaded ocean keeps the registry, so it can be saved and loaded again.
+    assert!(loaded.registry().contains("kelp"));
+    let bytes = loaded.to_bytes().unwrap();
+    let reloaded = Ocean::from_bytes_with(&bytes, loaded.registry()).unwrap();
+    assert!(reloaded.registry().contains("kelp"));
+    assert_eq!(records(&reloaded), records(&loaded));
+}
+
+#[test]
+fn snapshot_new_ids_after_load_are_fresh() {
+    let ocean = new_ocean();
+    let mut loaded = Ocean::from_json(&ocean.to_json().unwrap()).unwrap();
+
+    let max_id = loaded
+        .beaches()
+        .flat_map(|b| b.crabs())
+        .map(|c| c.id())
+        .max()
+        .unwrap();
+    let beach = loaded.beaches_mut().next().unwrap();
+    beach.add_crab(Crab::new(
+        String::from("Newbie"),
+        1,
+        Color::new_red(),
+        Diet::Fish,
+    ));
+    assert!(beach.crabs().last().unwrap().id() > max_id);
+}
+
+#[test]
+fn snapshot_same_seed_same_ids() {
+    // Crab ids come from each ocean, not from a counter shared by the whole program.
+    assert_eq!(
+        new_ocean().to_json().unwrap(),
+        new_ocean().to_json().unwrap()
+    );
+}
+
+#[test]
+fn snapshot_huge_crab_id_loads() {
+    let json = new_ocean().to_json().unwrap();
+    let key = "\"next_crab_id\":";
+    let start = json.find(key).unwrap() + key.len();
+    let end = start + json[start..].find(',').unwrap();
+    let json = format!("{}{}{}", &json[..start], u64::MAX, &json[end..]);
+    let mut loaded = Ocean::from_json(&json).unwrap();
+
+    let beach = loaded.beaches_mut().next().unwrap();
+    beach.add_crab(Crab::new(
+        String::from("Newbie"),
+        1,
+        Color::new_red(),
+        Diet::Fish,
+    ));
+    assert_ne!(beach.crabs().last().unwrap().id(), CrabId::UNASSIGNED);
+}
+
+#[test]
+fn snapshot_invalid_cookbook_fails_to_load() {
+    let json = new_ocean().to_json().unwrap();
+    let json = json.replace(r#""diets":["Shellfish"]"#, r#""diets":[]"#);
+    assert!(matches!(
+        Ocean::from_json(&json),
+        Err(SnapshotError::InvalidCookbook(CookbookError::NoDiets { recipe })) if recipe == "chowder"
+    ));
+}
+
+#[test]
+fn snapshot_custom_strategy_needs_registry() {
+    let mut ocean = Ocean::new();
+    let mut beach = Beach::new();
+    beach.add_crab(
+        Crab::new(String::from("Drifter"), 5, Color::new_red(), Diet::Fish)
+            .with_strategy(Box::new(Wander)),
+    );
+    ocean.add_beach(beach);
+
+    // An unregistered strategy could not be loaded again, so saving fails.
+    assert!(matches!(
+        ocean.to_json(),
+        Err(SnapshotError::InvalidStrategy(_))
+    ));
+    assert!(matches!(
+        ocean.to_bytes(),
+        Err(SnapshotError::InvalidStrategy(_))
+    ));
+
+    ocean
+        .strategy_registry_mut()
+        .register("wander", |_| Some(Box::new(Wander)));
+    let prey = PreyRegistry::new();
+    let loaded = Ocean::from_json_with_strategies(
+        &ocean.to_json().unwrap(),
+        &prey,
+        ocean.strategy_registry(),
+    )
+    .unwrap();
+
+    // The loaded ocean keeps the registry, so it can be saved and loaded again.
+    let reloaded = Ocean::from_bytes_with_strategies(
+        &loaded.to_bytes().unwrap(),
+        &prey,
+        loaded.strategy_registry(),
+    )
+    .unwrap();
+    let crab = reloaded.beaches().next().unwrap().get_crab(0);
+    assert_eq!(crab.strategy().to_record().tag(), "wander");
+}
@@ -0,0 +1,160 @@
+extern crate ocean;
+
+/*
+ * Run `cargo test strategy_` to run all of the hunting strategy tests.
+ */
+
+use ocean::color::*;
+use ocean::crab::*;
+use ocean::diet::*;
+use ocean::prey::*;
+use ocean::reef::*;
+use ocean::shared::*;
+use ocean::strategy::*;
+
+/* Fixtures */
+
+fn new_clam_eater(strategy: Box<dyn HuntStrategy>) -> Crab {
+    Crab::new(
+        String::from("Clawdia"),
+        20,
+        Color::new_blue(),
+        Diet::Shellfish,
+    )
+    .with_strategy(strategy)
+}
+
+fn new_reef(clams: usize, algae: usize) -> SharedReef {
+    let mut reef = Reef::new();
+    for _ in 0..algae {
+        reef.add_prey(Box::new(Algae::new()));
+    }
+    for _ in 0..clams {
+        reef.add_prey(Box::new(Clam::new()));
+    }
+    share(reef)
+}
+
+fn populations(reefs: &[SharedReef]) -> Vec<usize> {
+    reefs.iter().map(|r| read(r).population()).collect()
+}
+
+/* Tests */
+
+#[test]
+fn strategy_in_order_is_default() {
+    let mut crab = Crab::new(
+        String::from("Pinchy"),
+        20,
+        Color::new_blue(),
+        Diet::Shellfish,
+    );
+    assert_eq!(crab.strategy().to_record(), InOrder.to_record());
+
+    // The algae in the first reef is inedible, so the crab moves on to the second.
+    let reefs = [new_reef(0, 1), new_reef(1, 0)];
+    for reef in &reefs {
+        crab.discover_reef(SharedReef::clone(reef));
+    }
+    assert!(crab.hunt());
+    assert_eq!(populations(&reefs), vec![1, 0]);
+}
+
+#[test]
+fn strategy_richest_first() {
+    let mut crab = new_clam_eater(Box::new(RichestFirst));
+    let reefs = [new_reef(1, 0), new_reef(3, 0), new_reef(3, 0)];
+    for reef in &reefs {
+        crab.discover_reef(SharedReef::clone(reef));
+    }
+
+    assert!(crab.hunt());
+    assert_eq!(populations(&reefs), vec![1, 2, 3]);
+    assert!(crab.hunt());
+    assert_eq!(populations(&reefs), vec![1, 2, 2]);
+}
+
+#[test]
+fn strategy_round_robin() {
+    let mut crab = new_clam_eater(Box::new(RoundRobin::new()));
+    let reefs = [new_reef(2, 0), new_reef(0, 0), new_reef(2, 0)];
+    for reef in &reefs {
+        crab.discover_reef(SharedReef::clone(reef));
+    }
+
+    // Empty reefs are skipped, and the turn carries over between hunts.
+    assert!(crab.hunt());
+    assert_eq!(populations(&reefs), vec![1, 0, 2]);
+    assert!(crab.hunt());
+    assert_eq!(populations(&reefs), vec![1, 0, 1]);
+    assert!(crab.hunt());
+    assert_eq!(populations(&reefs), vec![0, 0, 1]);
+}
+
+#[test]
+fn strategy_remember_success() {
+    let mut crab = new_clam_eater(Box::new(RememberSuccess::new()));
+    let reefs = [new_reef(0, 1), new_reef(2, 0)];
+    for reef in &reefs {
+        crab.discover_reef(SharedReef::clone(reef));
+    }
+
+    assert!(crab.hunt());
+    assert_eq!(populations(&reefs), vec![1, 1]);
+
+    // Even with a clam in the first reef, the crab goes back to the second...
+    write(&reefs[0]).add_prey(Box::new(Clam::new()));
+    assert!(crab.hunt());
+    assert_eq!(populations(&reefs), vec![2, 0]);
+
+    // ...until the second reef runs out.
+    assert!(crab.hunt());
+    assert_eq!(populations(&reefs), vec![1, 0]);
+}
+
+#[test]
+fn strategy_give_up_after() {
+    let mut crab = new_clam_eater(Box::new(GiveUpAfter::new(2)));
+    let reefs = [new_reef(0, 2), new_reef(1, 0)];
+    for reef in &reefs {
+        crab.discover_reef(SharedReef::clone(reef));
+    }
+
+    // Two inedible algae is all the crab is willing to try.
+    assert!(!crab.hunt());
+    assert!(crab.is_hungry());
+    assert_eq!(populations(&reefs), vec![2, 1]);
+
+    crab.set_strategy(Box::new(GiveUpAfter::new(3)));
+    assert!(crab.hunt());
+    assert_eq!(populations(&reefs), vec![2, 0]);
+}
+
+#[test]
+fn strategy_no_reefs() {
+    let mut crab = new_clam_eater(Box::new(RoundRobin::new()));
+    assert!(!crab.hunt());
+    assert!(crab.is_hungry());
+}
+
+#[test]
+fn strategy_records_round_trip() {
+    let mut remember = RememberSuccess::new();
+    remember.record_catch(3);
+    let mut round_robin = RoundRobin::new();
+    round_robin.choose_reef(&[1, 1], 0);
+
+    let strategies: Vec<Box<dyn HuntStrategy>> = vec![
+        Box::new(InOrder),
+        Box::new(round_robin),
+        Box::new(RichestFirst),
+        Box::new(RememberSuccess::new()),
+        Box::new(remember),
+        Box::new(GiveUpAfter::new(4)),
+    ];
+    for s in &strategies {
+        let record = s.to_record();
+        assert_eq!(from_record(&record).unwrap().to_record(), record);
+    }
+    assert!(from_record(&StrategyRecord::new("wander")).is_none());
+}
@@ -0,0 +1,112 @@
+extern crate ocean;
+
+/*
+ * Run `cargo test sweep` to run all of the parameter sweep tests.
+ */
+
+use ocean::color::*;
+use ocean::diet::*;
+use ocean::scenario::*;
+use ocean::sweep::*;
+
+/* Fixtures */
+
+/**
+ * A single fish-eating crab with speed 20, hunting in a reef of minnows.
+ */
+fn new_minnow_scenario() -> Scenario {
+    Scenario {
+        reefs: vec![ReefScenario {
+            minnows: 10,
+            ..ReefScenario::default()
+        }],
+        beaches: vec![BeachScenario {
+            crabs: vec![CrabScenario {
+                name: String::from("Nemo's Nemesis"),
+                speed: 20,
+                color: Color::new_blue(),
+                diet: Diet::Fish,
+                diets: None,
+                reefs: vec![0],
+            }],
+            ..BeachScenario::default()
+        }],
+        ..Scenario::default()
+    }
+}
+
+/* Tests */
+
+#[test]
+fn sweep_rows_cover_combinations() {
+    let table = Sweep::new(new_minnow_scenario(), 5)
+        .minnow_speeds([10, 30])
+        .shrimp_energies([1, 2])
+        .seeds(0..3)
+        .run()
+        .unwrap();
+
+    let combinations: Vec<(u32, u32, usize, usize)> = table
+        .rows()
+        .iter()
+        .map(|r| (r.minnow_speed, r.shrimp_energy, r.reef_count, r.runs))
+        .collect();
+    assert_eq!(
+        combinations,
+        vec![(10, 1, 1, 3), (10, 2, 1, 3), (30, 1, 1, 3), (30, 2, 1, 3)]
+    );
+}
+
+#[test]
+fn sweep_minnow_speed_affects_hunting() {
+    let table = Sweep::new(new_minnow_scenario(), 20)
+        .minnow_speeds([10, 30])
+        .run()
+        .unwrap();
+    let (slow, fast) = (&table.rows()[0], &table.rows()[1]);
+
+    // Slow minnows are always caught; fast minnows always escape, and the crab starves.
+    assert_eq!(slow.hunt_success, 1.0);
+    assert_eq!(slow.survival, 1.0);
+    assert_eq!(fast.hunt_success, 0.0);
+    assert_eq!(fast.survival, 0.0);
+}
+
+#[test]
+fn sweep_reef_counts_repeat_base_reefs() {
+    let sweep = Sweep::new(new_minnow_scenario(), 1);
+    let scenario = sweep.scenario(25, 1, 3, 0);
+
+    assert_eq!(scenario.reefs.len(), 3);
+    assert_eq!(scenario.beaches[0].crabs[0].reefs, vec![0, 1, 2]);
+
+    let table = sweep.reef_counts([1, 3]).run().unwrap();
+    assert!(table.rows()[0].prey_remaining < table.rows()[1].prey_remaining);
+}
+
+#[test]
+fn sweep_reef_counts_need_base_reefs() {
+    let mut base = new_minnow_scenario();
+    base.reefs.clear();
+    base.beaches[0].crabs[0].reefs.clear();
+
+    assert!(Sweep::new(base.clone(), 1).run().is_ok());
+    assert!(matches!(
+        Sweep::new(base, 1).reef_counts([0, 2]).run(),
+        Err(ScenarioError::NoReefsToRepeat { reef_count: 2 })
+    ));
+}
+
+#[test]
+fn sweep_to_csv() {
+    let table = Sweep::new(new_minnow_scenario(), 3)
+        .seeds([1, 2])
+        .run()
+        .unwrap();
+
+    let csv = table.to_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], SweepTable::CSV_HEADER);
+    assert_eq!(lines.len(), 2);
+    assert!(lines[1].starts_with("25,1,1,2,"));
+}
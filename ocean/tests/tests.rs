@@ -1,2 +1,24 @@
+mod aging;
+mod cookbook;
+mod cooking;
+mod crowding;
+mod diet;
+mod errors;
+mod exploration;
+mod genetics;
+mod grid;
+mod hunt;
+mod migration;
+mod pantry;
+mod parallel;
+mod pedigree;
+mod prey;
 mod public;
+mod random;
+mod reef;
+mod scenario;
+mod simulation;
+mod snapshot;
+mod strategy;
 mod student;
+mod sweep;